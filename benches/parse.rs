@@ -18,6 +18,19 @@ fn parse_feature_collection_benchmark(c: &mut Criterion) {
         })
     });
 
+    // This crate writes the members of objects in order of their names, so `type` comes last.
+    let type_last_str = geojson_str.parse::<GeoJson>().unwrap().to_string();
+
+    c.bench_function("parse with `type` last (countries.geojson)", |b| {
+        b.iter(|| match type_last_str.parse::<geojson::GeoJson>() {
+            Ok(GeoJson::FeatureCollection(fc)) => {
+                assert_eq!(fc.features.len(), 180);
+                black_box(fc)
+            }
+            _ => panic!("unexpected result"),
+        })
+    });
+
     c.bench_function("FeatureReader::features (countries.geojson)", |b| {
         b.iter(|| {
             let feature_reader =
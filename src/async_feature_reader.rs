@@ -0,0 +1,288 @@
+use crate::{Feature, FeatureCollectionMembers, FeatureParser, Result};
+
+use futures_core::{ready, Stream};
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+const BUFFER_SIZE: usize = 8 * 1024;
+
+/// Enumerates individual Features from a GeoJSON FeatureCollection read from an
+/// [`AsyncRead`](tokio::io::AsyncRead).
+///
+/// This is the asynchronous counterpart of [`FeatureReader`](crate::FeatureReader), available
+/// with the `tokio` feature.
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub struct AsyncFeatureReader<R> {
+    reader: R,
+}
+
+impl<R: AsyncRead + Unpin> AsyncFeatureReader<R> {
+    /// Create an AsyncFeatureReader from the given `reader`.
+    pub fn from_reader(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Stream the individual [`Feature`s](Feature) of the FeatureCollection.
+    ///
+    /// If instead you'd like to deserialize directly to your own struct, see
+    /// [`AsyncFeatureReader::deserialize`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// use futures_util::StreamExt;
+    /// use geojson::AsyncFeatureReader;
+    ///
+    /// let feature_collection_string = r#"{
+    ///      "type": "FeatureCollection",
+    ///      "features": [
+    ///          {
+    ///            "type": "Feature",
+    ///            "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+    ///            "properties": { "name": "Dinagat Islands" }
+    ///          },
+    ///          {
+    ///            "type": "Feature",
+    ///            "geometry": { "type": "Point", "coordinates": [2.3, 4.5] },
+    ///            "properties": { "name": "Neverland" }
+    ///          }
+    ///      ]
+    /// }"#;
+    ///
+    /// let reader = AsyncFeatureReader::from_reader(feature_collection_string.as_bytes());
+    /// let mut features = reader.features();
+    /// let mut names = vec![];
+    /// while let Some(feature) = features.next().await {
+    ///     let feature = feature.expect("valid geojson feature");
+    ///     names.push(feature.property("name").unwrap().as_str().unwrap().to_string());
+    /// }
+    /// assert_eq!(names, vec!["Dinagat Islands", "Neverland"]);
+    /// # }
+    /// ```
+    pub fn features(self) -> FeatureStream<R, Feature> {
+        FeatureStream::new(self.reader, FeatureParser::new())
+    }
+
+    /// Deserialize the features of the FeatureCollection into your own custom struct using the
+    /// [`serde`](../../serde) crate.
+    ///
+    /// See [`FeatureReader::deserialize`](crate::FeatureReader::deserialize) for the
+    /// requirements on your struct.
+    ///
+    /// # Examples
+    #[cfg_attr(feature = "geo-types", doc = "```")]
+    #[cfg_attr(not(feature = "geo-types"), doc = "```ignore")]
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// use futures_util::StreamExt;
+    /// use geojson::{AsyncFeatureReader, de::deserialize_geometry};
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct MyStruct {
+    ///     #[serde(deserialize_with = "deserialize_geometry")]
+    ///     geometry: geo_types::Point<f64>,
+    ///     name: String,
+    /// }
+    ///
+    /// let feature_collection_string = r#"{
+    ///      "type": "FeatureCollection",
+    ///      "features": [
+    ///          {
+    ///            "type": "Feature",
+    ///            "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+    ///            "properties": { "name": "Dinagat Islands" }
+    ///          }
+    ///      ]
+    /// }"#;
+    ///
+    /// let reader = AsyncFeatureReader::from_reader(feature_collection_string.as_bytes());
+    /// let mut my_structs = reader.deserialize::<MyStruct>().unwrap();
+    /// while let Some(my_struct) = my_structs.next().await {
+    ///     let my_struct = my_struct.expect("valid geojson feature");
+    ///     assert_eq!(my_struct.name, "Dinagat Islands");
+    ///     assert_eq!(my_struct.geometry.x(), 125.6);
+    /// }
+    /// # }
+    /// ```
+    pub fn deserialize<D: DeserializeOwned>(self) -> Result<FeatureStream<R, D>> {
+        Ok(FeatureStream::new(
+            self.reader,
+            FeatureParser::deserialize(),
+        ))
+    }
+}
+
+/// A [`Stream`] over the features of a FeatureCollection, returned by
+/// [`AsyncFeatureReader::features`] and [`AsyncFeatureReader::deserialize`].
+///
+/// Any error ends the stream, since the input can't be resynchronized afterwards.
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub struct FeatureStream<R, D> {
+    reader: R,
+    parser: FeatureParser<D>,
+    pending: VecDeque<Result<D>>,
+    buf: Box<[u8]>,
+    done: bool,
+}
+
+impl<R, D> FeatureStream<R, D> {
+    fn new(reader: R, parser: FeatureParser<D>) -> Self {
+        FeatureStream {
+            reader,
+            parser,
+            pending: VecDeque::new(),
+            buf: vec![0; BUFFER_SIZE].into_boxed_slice(),
+            done: false,
+        }
+    }
+
+    /// The `bbox` and foreign members of the FeatureCollection which have been read so far.
+    ///
+    /// Members which precede the `features` array are available once the first feature has
+    /// been produced, and members which follow it once the stream has ended.
+    pub fn collection_members(&self) -> &FeatureCollectionMembers {
+        self.parser.collection_members()
+    }
+}
+
+// Features are never pinned, they're only moved out of `pending`.
+impl<R: Unpin, D> Unpin for FeatureStream<R, D> {}
+
+impl<R: AsyncRead + Unpin, D> Stream for FeatureStream<R, D> {
+    type Item = Result<D>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(result) = this.pending.pop_front() {
+                return Poll::Ready(Some(result));
+            }
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            let mut read_buf = ReadBuf::new(&mut this.buf);
+            if let Err(err) = ready!(Pin::new(&mut this.reader).poll_read(cx, &mut read_buf)) {
+                this.done = true;
+                return Poll::Ready(Some(Err(err.into())));
+            }
+            let filled = read_buf.filled();
+            if filled.is_empty() {
+                this.done = true;
+                if let Err(err) = this.parser.finish() {
+                    this.pending.push_back(Err(err));
+                }
+            } else {
+                this.pending.extend(this.parser.feed(filled));
+                this.done = this.parser.is_failed();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Geometry, Value};
+
+    use futures_util::StreamExt;
+    use serde::Deserialize;
+    use tokio::io::AsyncWriteExt;
+
+    #[derive(Deserialize)]
+    struct MyRecord {
+        geometry: Geometry,
+        name: String,
+        age: u64,
+    }
+
+    fn fc() -> &'static str {
+        r#"{
+            "bbox": [2.3, 4.5, 125.6, 10.1],
+            "type": "FeatureCollection",
+            "features": [
+                {
+                  "type": "Feature",
+                  "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+                  "properties": { "name": "Dinagat Islands", "age": 123 }
+                },
+                {
+                  "type": "Feature",
+                  "geometry": { "type": "Point", "coordinates": [2.3, 4.5] },
+                  "properties": { "name": "Neverland", "age": 456 }
+                }
+            ],
+            "name": "islands"
+        }"#
+    }
+
+    /// Write `input` to the other end of a small duplex pipe, a few bytes at a time.
+    fn piped(input: &'static str) -> tokio::io::DuplexStream {
+        let (mut writer, reader) = tokio::io::duplex(16);
+        tokio::spawn(async move {
+            for chunk in input.as_bytes().chunks(7) {
+                writer.write_all(chunk).await.unwrap();
+            }
+        });
+        reader
+    }
+
+    #[tokio::test]
+    async fn stream_features() {
+        let mut features = AsyncFeatureReader::from_reader(piped(fc())).features();
+
+        let first = features.next().await.unwrap().unwrap();
+        assert_eq!(first.property("name").unwrap(), "Dinagat Islands");
+        assert_eq!(
+            features.collection_members().bbox,
+            Some(vec![2.3, 4.5, 125.6, 10.1])
+        );
+        assert!(features.collection_members().foreign_members.is_none());
+
+        let second = features.next().await.unwrap().unwrap();
+        assert_eq!(second.property("name").unwrap(), "Neverland");
+        assert!(features.next().await.is_none());
+        assert_eq!(
+            features
+                .collection_members()
+                .foreign_members
+                .as_ref()
+                .unwrap()
+                .get("name")
+                .unwrap(),
+            "islands"
+        );
+    }
+
+    #[tokio::test]
+    async fn deserialize_into_type() {
+        let records: Vec<MyRecord> = AsyncFeatureReader::from_reader(piped(fc()))
+            .deserialize()
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name, "Dinagat Islands");
+        assert_eq!(records[1].age, 456);
+        assert_eq!(
+            records[1].geometry,
+            Geometry::new(Value::Point(vec![2.3, 4.5]))
+        );
+    }
+
+    #[tokio::test]
+    async fn truncated_input() {
+        let input = &fc()[..fc().find("Neverland").unwrap()];
+        let mut features = AsyncFeatureReader::from_reader(input.as_bytes()).features();
+        assert!(features.next().await.unwrap().is_ok());
+        assert!(features.next().await.unwrap().is_err());
+        assert!(features.next().await.is_none());
+    }
+}
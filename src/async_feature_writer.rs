@@ -0,0 +1,280 @@
+use crate::ser::to_feature_writer;
+use crate::{Error, Feature, Result};
+
+use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+#[derive(PartialEq)]
+enum State {
+    New,
+    Started,
+    Finished,
+}
+
+/// Write Features to a FeatureCollection on an [`AsyncWrite`](tokio::io::AsyncWrite).
+///
+/// This is the asynchronous counterpart of [`FeatureWriter`](crate::FeatureWriter), available
+/// with the `tokio` feature.
+///
+/// Unlike `FeatureWriter`, this writer can't close the FeatureCollection when it's dropped, so
+/// you must call [`AsyncFeatureWriter::finish`] once all features have been written, otherwise
+/// the output is incomplete.
+///
+/// Each Feature is serialized into an internal buffer before being written, so the
+/// underlying writer only receives whole Features.
+///
+/// # Examples
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use geojson::{AsyncFeatureWriter, Feature, Value};
+///
+/// let mut output: Vec<u8> = vec![];
+/// let mut writer = AsyncFeatureWriter::from_writer(&mut output);
+/// writer
+///     .write_feature(&Feature::from(Value::Point(vec![1.0, 2.0])))
+///     .await
+///     .unwrap();
+/// writer.finish().await.unwrap();
+///
+/// let feature_collection: geojson::FeatureCollection =
+///     serde_json::from_slice(&output).unwrap();
+/// assert_eq!(feature_collection.features.len(), 1);
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub struct AsyncFeatureWriter<W: AsyncWrite + Unpin> {
+    writer: W,
+    state: State,
+    buf: Vec<u8>,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncFeatureWriter<W> {
+    /// Create an AsyncFeatureWriter from the given `writer`.
+    ///
+    /// To append features from your custom structs, use [`AsyncFeatureWriter::serialize`].
+    ///
+    /// To append features from [`Feature`] use [`AsyncFeatureWriter::write_feature`].
+    pub fn from_writer(writer: W) -> Self {
+        Self {
+            writer,
+            state: State::New,
+            buf: vec![],
+        }
+    }
+
+    /// Write a [`crate::Feature`] struct to the output stream. If you'd like to
+    /// serialize your own custom structs, see [`AsyncFeatureWriter::serialize`] instead.
+    pub async fn write_feature(&mut self, feature: &Feature) -> Result<()> {
+        self.start_feature("cannot write another Feature when writer has already finished")?;
+        if let Err(err) = serde_json::to_writer(&mut self.buf, feature) {
+            self.buf.clear();
+            return Err(err.into());
+        }
+        self.state = State::Started;
+        self.write_buf().await
+    }
+
+    /// Serialize your own custom struct to the features of a FeatureCollection using the
+    /// [`serde`] crate.
+    ///
+    /// See [`FeatureWriter::serialize`](crate::FeatureWriter::serialize) for the requirements
+    /// on your struct.
+    pub async fn serialize<S: Serialize>(&mut self, value: &S) -> Result<()> {
+        self.start_feature("cannot serialize another record when writer has already finished")?;
+        if let Err(err) = to_feature_writer(&mut self.buf, value) {
+            self.buf.clear();
+            return Err(err);
+        }
+        self.state = State::Started;
+        self.write_buf().await
+    }
+
+    /// Writes the closing syntax for the FeatureCollection, and flushes the underlying writer.
+    pub async fn finish(&mut self) -> Result<()> {
+        match self.state {
+            State::Finished => {
+                return Err(Error::InvalidWriterState(
+                    "cannot finish writer - it's already finished",
+                ))
+            }
+            State::New => {
+                self.state = State::Finished;
+                self.write_prefix();
+                self.buf.extend_from_slice(b"]}");
+            }
+            State::Started => {
+                self.state = State::Finished;
+                self.buf.extend_from_slice(b"]}");
+            }
+        }
+        self.write_buf().await?;
+        self.flush().await
+    }
+
+    /// Flush the underlying writer buffer.
+    pub async fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush().await?)
+    }
+
+    /// Unwrap the underlying writer, e.g. to shut it down after calling
+    /// [`AsyncFeatureWriter::finish`].
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Write whatever needs to precede the next feature into the internal buffer.
+    ///
+    /// The state only becomes `Started` once the feature has been serialized too, since the
+    /// buffer is cleared if that fails.
+    fn start_feature(&mut self, finished_message: &'static str) -> Result<()> {
+        match self.state {
+            State::Finished => return Err(Error::InvalidWriterState(finished_message)),
+            State::New => self.write_prefix(),
+            State::Started => self.buf.push(b','),
+        }
+        Ok(())
+    }
+
+    fn write_prefix(&mut self) {
+        self.buf
+            .extend_from_slice(br#"{ "type": "FeatureCollection", "features": ["#);
+    }
+
+    async fn write_buf(&mut self) -> Result<()> {
+        let result = self.writer.write_all(&self.buf).await;
+        self.buf.clear();
+        Ok(result?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AsyncFeatureReader, Geometry, JsonValue, Value};
+
+    use futures_util::StreamExt;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct MyRecord {
+        geometry: Geometry,
+        name: String,
+        age: u64,
+    }
+
+    #[tokio::test]
+    async fn write_empty() {
+        let mut buffer: Vec<u8> = vec![];
+        let mut writer = AsyncFeatureWriter::from_writer(&mut buffer);
+        writer.finish().await.unwrap();
+        assert!(writer.finish().await.is_err());
+
+        let expected = json!({
+            "type": "FeatureCollection",
+            "features": []
+        });
+        let actual_json: JsonValue = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(actual_json, expected);
+    }
+
+    #[tokio::test]
+    async fn write_and_serialize() {
+        let mut buffer: Vec<u8> = vec![];
+        let mut writer = AsyncFeatureWriter::from_writer(&mut buffer);
+        let mut feature = Feature::from(Value::Point(vec![1.1, 1.2]));
+        feature.set_property("name", "Mishka");
+        feature.set_property("age", 12);
+        writer.write_feature(&feature).await.unwrap();
+        writer
+            .serialize(&MyRecord {
+                geometry: Geometry::from(Value::Point(vec![2.1, 2.2])),
+                name: "Jane".to_string(),
+                age: 22,
+            })
+            .await
+            .unwrap();
+        writer.finish().await.unwrap();
+        assert!(writer.write_feature(&feature).await.is_err());
+
+        let expected = json!({
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "Point", "coordinates": [1.1, 1.2] },
+                    "properties": { "name": "Mishka", "age": 12 }
+                },
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "Point", "coordinates": [2.1, 2.2] },
+                    "properties": { "name": "Jane", "age": 22 }
+                }
+            ]
+        });
+        let actual_json: JsonValue = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(actual_json, expected);
+    }
+
+    #[tokio::test]
+    async fn serialize_error() {
+        struct Unserializable;
+
+        impl Serialize for Unserializable {
+            fn serialize<S>(&self, _serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                Err(serde::ser::Error::custom("can't be serialized"))
+            }
+        }
+
+        #[derive(Serialize)]
+        struct BadRecord {
+            geometry: Geometry,
+            name: Unserializable,
+        }
+
+        let mut buffer: Vec<u8> = vec![];
+        let mut writer = AsyncFeatureWriter::from_writer(&mut buffer);
+        let bad_record = BadRecord {
+            geometry: Geometry::from(Value::Point(vec![1.0, 2.0])),
+            name: Unserializable,
+        };
+        // Failing on the first and on a later feature both leave the output intact.
+        assert!(writer.serialize(&bad_record).await.is_err());
+        let feature = Feature::from(Value::Point(vec![1.0, 2.0]));
+        writer.write_feature(&feature).await.unwrap();
+        assert!(writer.serialize(&bad_record).await.is_err());
+        writer.write_feature(&feature).await.unwrap();
+        writer.finish().await.unwrap();
+
+        let feature_collection: crate::FeatureCollection = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(feature_collection.features.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn write_through_pipe() {
+        let (writer, reader) = tokio::io::duplex(16);
+        let write = tokio::spawn(async move {
+            let mut writer = AsyncFeatureWriter::from_writer(writer);
+            for i in 0..10 {
+                let mut feature = Feature::from(Value::Point(vec![1.0, 2.0]));
+                feature.set_property("i", i);
+                writer.write_feature(&feature).await.unwrap();
+            }
+            writer.finish().await.unwrap();
+            writer.into_inner().shutdown().await.unwrap();
+        });
+
+        let features: Vec<Feature> = AsyncFeatureReader::from_reader(reader)
+            .features()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        write.await.unwrap();
+        assert_eq!(features.len(), 10);
+        assert_eq!(features[9].property("i").unwrap(), 9);
+    }
+}
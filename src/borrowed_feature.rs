@@ -0,0 +1,393 @@
+use crate::errors::{Error, Result};
+use crate::{Feature, JsonObject, JsonValue};
+
+use serde::de::{self, Deserialize, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// The members of a JSON object, borrowed from the input they were parsed from.
+///
+/// Names are only copied if they contain escape sequences.
+pub type BorrowedObject<'a> = BTreeMap<Cow<'a, str>, &'a RawValue>;
+
+/// A Feature which borrows from the JSON text it was parsed from, rather than copying it.
+///
+/// Only the structure of the Feature is parsed: the geometry and every property value are kept as
+/// unparsed [`RawValue`]s, so a BorrowedFeature is cheap to build, inspect and serialize again
+/// unchanged. This suits read-only processing like filtering, where most of a Feature is passed
+/// through without being looked at. Use [`BorrowedFeature::to_feature`] to get an owned [`Feature`]
+/// on demand.
+///
+/// A BorrowedFeature can only be deserialized from input which outlives it, like a `&str` passed
+/// to [`serde_json::from_str`]. To read the features of a FeatureCollection, see
+/// [`de::borrowed_features_from_str`](crate::de::borrowed_features_from_str).
+///
+/// # Examples
+///
+/// ```
+/// use geojson::BorrowedFeature;
+///
+/// let feature_str = r#"{
+///     "type": "Feature",
+///     "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+///     "properties": { "name": "Dinagat Islands", "population": 127152 }
+/// }"#;
+///
+/// let borrowed: BorrowedFeature = serde_json::from_str(feature_str).unwrap();
+/// assert_eq!(borrowed.property("name").unwrap().get(), r#""Dinagat Islands""#);
+///
+/// let feature = borrowed.to_feature().unwrap();
+/// assert_eq!(feature.property("population").unwrap(), 127152);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BorrowedFeature<'a> {
+    /// Bounding Box
+    ///
+    /// [GeoJSON Format Specification § 5](https://tools.ietf.org/html/rfc7946#section-5)
+    pub bbox: Option<&'a RawValue>,
+    /// Geometry, or `None` if it's `null`
+    ///
+    /// [GeoJSON Format Specification § 3.2](https://tools.ietf.org/html/rfc7946#section-3.2)
+    pub geometry: Option<&'a RawValue>,
+    /// Identifier
+    ///
+    /// [GeoJSON Format Specification § 3.2](https://tools.ietf.org/html/rfc7946#section-3.2)
+    pub id: Option<&'a RawValue>,
+    /// Properties
+    ///
+    /// [GeoJSON Format Specification § 3.2](https://tools.ietf.org/html/rfc7946#section-3.2)
+    pub properties: Option<BorrowedObject<'a>>,
+    /// Foreign Members
+    ///
+    /// [GeoJSON Format Specification § 6](https://tools.ietf.org/html/rfc7946#section-6)
+    pub foreign_members: Option<BorrowedObject<'a>>,
+}
+
+impl<'a> BorrowedFeature<'a> {
+    /// Return the unparsed value of a property, if it exists.
+    pub fn property(&self, key: impl AsRef<str>) -> Option<&'a RawValue> {
+        self.properties
+            .as_ref()
+            .and_then(|props| props.get(key.as_ref()).copied())
+    }
+
+    /// Return true iff the feature has a property named `key`.
+    pub fn contains_property(&self, key: &str) -> bool {
+        self.property(key).is_some()
+    }
+
+    /// Parse the borrowed members into an owned [`Feature`].
+    ///
+    /// This fails for the same input which [`Feature::from_json_object`] rejects, e.g. if the
+    /// geometry is invalid.
+    pub fn to_feature(&self) -> Result<Feature> {
+        Feature::try_from(self)
+    }
+}
+
+impl TryFrom<&BorrowedFeature<'_>> for Feature {
+    type Error = Error;
+
+    fn try_from(borrowed: &BorrowedFeature<'_>) -> Result<Self> {
+        fn parse(raw: &RawValue) -> Result<JsonValue> {
+            Ok(serde_json::from_str(raw.get())?)
+        }
+        fn parse_object(object: &BorrowedObject<'_>) -> Result<JsonObject> {
+            object
+                .iter()
+                .map(|(key, raw)| Ok((key.to_string(), parse(raw)?)))
+                .collect()
+        }
+
+        let mut object = match &borrowed.foreign_members {
+            Some(foreign_members) => parse_object(foreign_members)?,
+            None => JsonObject::new(),
+        };
+        object.insert("type".to_string(), JsonValue::from("Feature"));
+        let geometry = borrowed.geometry.map(parse).transpose()?;
+        object.insert("geometry".to_string(), geometry.unwrap_or(JsonValue::Null));
+        if let Some(bbox) = borrowed.bbox {
+            object.insert("bbox".to_string(), parse(bbox)?);
+        }
+        if let Some(id) = borrowed.id {
+            object.insert("id".to_string(), parse(id)?);
+        }
+        if let Some(properties) = &borrowed.properties {
+            object.insert(
+                "properties".to_string(),
+                JsonValue::Object(parse_object(properties)?),
+            );
+        }
+        Feature::from_json_object(object)
+    }
+}
+
+impl<'a> TryFrom<BorrowedFeature<'a>> for Feature {
+    type Error = Error;
+
+    fn try_from(borrowed: BorrowedFeature<'a>) -> Result<Self> {
+        Feature::try_from(&borrowed)
+    }
+}
+
+impl Serialize for BorrowedFeature<'_> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", "Feature")?;
+        if let Some(bbox) = self.bbox {
+            map.serialize_entry("bbox", bbox)?;
+        }
+        map.serialize_entry("geometry", &self.geometry)?;
+        if let Some(id) = self.id {
+            map.serialize_entry("id", id)?;
+        }
+        match &self.properties {
+            Some(properties) => map.serialize_entry("properties", properties)?,
+            None => map.serialize_entry("properties", &BorrowedObject::new())?,
+        }
+        if let Some(foreign_members) = &self.foreign_members {
+            for (key, value) in foreign_members {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for BorrowedFeature<'a> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(BorrowedFeatureVisitor(PhantomData))
+    }
+}
+
+/// A string which is borrowed from the input unless it contains escape sequences.
+#[derive(serde::Deserialize)]
+struct Key<'a>(#[serde(borrow)] Cow<'a, str>);
+
+/// Check that the `type` member of an object is `expected`.
+fn expect_type<'de, A: MapAccess<'de>>(
+    map: &mut A,
+    expected: &str,
+) -> std::result::Result<(), A::Error> {
+    let Key(actual) = map.next_value()?;
+    if actual != expected {
+        return Err(de::Error::custom(Error::ExpectedType {
+            expected: expected.to_string(),
+            actual: actual.into_owned(),
+        }));
+    }
+    Ok(())
+}
+
+struct BorrowedFeatureVisitor<'a>(PhantomData<&'a ()>);
+
+impl<'de: 'a, 'a> Visitor<'de> for BorrowedFeatureVisitor<'a> {
+    type Value = BorrowedFeature<'a>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a GeoJSON Feature")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut feature = BorrowedFeature::default();
+        let mut has_type = false;
+        let mut has_geometry = false;
+        let mut foreign_members = BorrowedObject::new();
+        while let Some(Key(key)) = map.next_key()? {
+            match key.as_ref() {
+                "type" => {
+                    expect_type(&mut map, "Feature")?;
+                    has_type = true;
+                }
+                "bbox" => feature.bbox = Some(map.next_value()?),
+                "geometry" => {
+                    feature.geometry = map.next_value()?;
+                    has_geometry = true;
+                }
+                "id" => feature.id = Some(map.next_value()?),
+                "properties" => {
+                    feature.properties = map.next_value::<Option<Object>>()?.map(|o| o.0);
+                }
+                _ => {
+                    foreign_members.insert(key, map.next_value()?);
+                }
+            }
+        }
+        if !has_type {
+            return Err(de::Error::custom(Error::ExpectedProperty(
+                "type".to_string(),
+            )));
+        }
+        if !has_geometry {
+            return Err(de::Error::custom(Error::ExpectedProperty(
+                "geometry".to_string(),
+            )));
+        }
+        if !foreign_members.is_empty() {
+            feature.foreign_members = Some(foreign_members);
+        }
+        Ok(feature)
+    }
+}
+
+/// A JSON object whose members are borrowed.
+struct Object<'a>(BorrowedObject<'a>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for Object<'a> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ObjectVisitor<'a>(PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for ObjectVisitor<'a> {
+            type Value = Object<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a JSON object")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut object = BorrowedObject::new();
+                while let Some((Key(key), value)) = map.next_entry()? {
+                    object.insert(key, value);
+                }
+                Ok(Object(object))
+            }
+        }
+
+        deserializer.deserialize_map(ObjectVisitor(PhantomData))
+    }
+}
+
+/// The features of a FeatureCollection, borrowed from its JSON text.
+pub(crate) struct BorrowedFeatures<'a>(pub(crate) Vec<BorrowedFeature<'a>>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for BorrowedFeatures<'a> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FeatureCollectionVisitor<'a>(PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for FeatureCollectionVisitor<'a> {
+            type Value = BorrowedFeatures<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a GeoJSON FeatureCollection")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut features = None;
+                while let Some(Key(key)) = map.next_key()? {
+                    match key.as_ref() {
+                        "type" => expect_type(&mut map, "FeatureCollection")?,
+                        "features" => features = Some(map.next_value()?),
+                        _ => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+                features.map(BorrowedFeatures).ok_or_else(|| {
+                    de::Error::custom(Error::ExpectedProperty("features".to_string()))
+                })
+            }
+        }
+
+        deserializer.deserialize_map(FeatureCollectionVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeoJson, Value};
+    use serde_json::json;
+
+    fn feature_str() -> &'static str {
+        r#"{
+            "type": "Feature",
+            "id": 12,
+            "bbox": [1.0, 2.0, 1.0, 2.0],
+            "geometry": { "type": "Point", "coordinates": [1.0, 2.0] },
+            "properties": { "name": "a\"b", "nested": { "c": [1, null] } },
+            "title": "there"
+        }"#
+    }
+
+    #[test]
+    fn borrows_from_input() {
+        let borrowed: BorrowedFeature = serde_json::from_str(feature_str()).unwrap();
+        assert_eq!(borrowed.id.unwrap().get(), "12");
+        assert_eq!(borrowed.property("name").unwrap().get(), r#""a\"b""#);
+        assert!(matches!(
+            borrowed.properties.as_ref().unwrap().keys().next().unwrap(),
+            Cow::Borrowed("name")
+        ));
+        assert_eq!(
+            borrowed.foreign_members.as_ref().unwrap()["title"].get(),
+            r#""there""#
+        );
+
+        let feature = borrowed.to_feature().unwrap();
+        assert_eq!(feature, feature_str().parse::<Feature>().unwrap());
+        assert_eq!(
+            feature.geometry.unwrap().value,
+            Value::Point(vec![1.0, 2.0])
+        );
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let borrowed: BorrowedFeature = serde_json::from_str(feature_str()).unwrap();
+        let output = serde_json::to_value(&borrowed).unwrap();
+        let expected = serde_json::to_value(feature_str().parse::<GeoJson>().unwrap()).unwrap();
+        assert_eq!(output, expected);
+
+        let no_properties: BorrowedFeature =
+            serde_json::from_str(r#"{"type": "Feature", "geometry": null}"#).unwrap();
+        assert_eq!(
+            serde_json::to_value(&no_properties).unwrap(),
+            json!({ "type": "Feature", "geometry": null, "properties": {} })
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let not_a_feature =
+            serde_json::from_str::<BorrowedFeature>(r#"{"type": "Point", "coordinates": [1, 2]}"#)
+                .unwrap_err();
+        assert!(not_a_feature
+            .to_string()
+            .contains("Expected GeoJSON type `Feature`, found `Point`"));
+        assert!(serde_json::from_str::<BorrowedFeature>(r#"{"type": "Feature"}"#).is_err());
+        assert!(serde_json::from_str::<BorrowedFeature>(
+            r#"{"type": "Feature", "geometry": null, "properties": 1}"#
+        )
+        .is_err());
+
+        let invalid_geometry: BorrowedFeature =
+            serde_json::from_str(r#"{"type": "Feature", "geometry": {"type": "Point"}}"#).unwrap();
+        assert!(invalid_geometry.to_feature().is_err());
+    }
+}
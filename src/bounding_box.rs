@@ -0,0 +1,572 @@
+use crate::errors::{Error, Result};
+use crate::util::Extent;
+use crate::{Bbox, Position, PositionExt, Value};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+
+/// A bounding box, with methods to combine and compare them.
+///
+/// The [`Bbox`] of a GeoJSON object can be converted to and from a `BoundingBox`, which is
+/// serialized as the same JSON array.
+///
+/// Following [RFC 7946 § 5.2](https://tools.ietf.org/html/rfc7946#section-5.2), a bounding box
+/// whose `west` is greater than its `east` crosses the antimeridian: it covers the longitudes from
+/// `west` to 180 and from -180 to `east`. Every method takes this into account.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::BoundingBox;
+/// use std::convert::TryFrom;
+///
+/// let fiji = BoundingBox::new(177.0, -20.0, -178.0, -16.0);
+/// assert!(fiji.crosses_antimeridian());
+/// assert!(fiji.contains_position(&[179.5, -18.0]));
+/// assert!(!fiji.contains_position(&[0.0, -18.0]));
+///
+/// let bbox = BoundingBox::try_from(&vec![100.0, 0.0, 105.0, 1.0]).unwrap();
+/// assert_eq!(bbox, BoundingBox::new(100.0, 0.0, 105.0, 1.0));
+/// assert_eq!(serde_json::to_string(&bbox).unwrap(), "[100.0,0.0,105.0,1.0]");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    /// The minimum longitude, or the longitude of the western edge if it crosses the antimeridian.
+    pub west: f64,
+    /// The minimum latitude.
+    pub south: f64,
+    /// The maximum longitude, or the longitude of the eastern edge if it crosses the antimeridian.
+    pub east: f64,
+    /// The maximum latitude.
+    pub north: f64,
+    /// The minimum and maximum altitude, if the bounding box has them.
+    pub z: Option<(f64, f64)>,
+}
+
+impl BoundingBox {
+    /// A two-dimensional bounding box.
+    pub fn new(west: f64, south: f64, east: f64, north: f64) -> Self {
+        BoundingBox {
+            west,
+            south,
+            east,
+            north,
+            z: None,
+        }
+    }
+
+    /// A three-dimensional bounding box, with its arguments in the order of the [`Bbox`].
+    pub fn new_3d(west: f64, south: f64, min_z: f64, east: f64, north: f64, max_z: f64) -> Self {
+        BoundingBox {
+            z: Some((min_z, max_z)),
+            ..BoundingBox::new(west, south, east, north)
+        }
+    }
+
+    /// The bounding box of a single position, which is three-dimensional if the position has a Z
+    /// value.
+    ///
+    /// # Panics
+    ///
+    /// If the position has fewer than 2 values.
+    pub fn from_position(position: &[f64]) -> Self {
+        BoundingBox {
+            z: position.z().map(|z| (z, z)),
+            ..BoundingBox::new(position.x(), position.y(), position.x(), position.y())
+        }
+    }
+
+    /// Whether the bounding box crosses the antimeridian, i.e. `west` is greater than `east`.
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.west > self.east
+    }
+
+    /// The values in the order of a [`Bbox`].
+    pub fn to_bbox(&self) -> Bbox {
+        match self.z {
+            Some((min_z, max_z)) => {
+                vec![self.west, self.south, min_z, self.east, self.north, max_z]
+            }
+            None => vec![self.west, self.south, self.east, self.north],
+        }
+    }
+
+    /// The smallest bounding box which contains both this one and `other`.
+    ///
+    /// If neither crosses the antimeridian, this is the minimum and maximum of each value, as for
+    /// projected coordinates. Otherwise, it's the narrower of the two ways of joining them around
+    /// the globe. The result only has Z values if both do.
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        let (west, east) = if !self.crosses_antimeridian() && !other.crosses_antimeridian() {
+            (self.west.min(other.west), self.east.max(other.east))
+        } else {
+            let (start, length) = self.longitude_arc();
+            let (other_start, other_length) = other.longitude_arc();
+            let eastward = length.max(offset(start, other_start) + other_length);
+            let westward = other_length.max(offset(other_start, start) + length);
+            if eastward <= westward {
+                longitudes(start, eastward)
+            } else {
+                longitudes(other_start, westward)
+            }
+        };
+        BoundingBox {
+            west,
+            south: self.south.min(other.south),
+            east,
+            north: self.north.max(other.north),
+            z: match (self.z, other.z) {
+                (Some((min, max)), Some((other_min, other_max))) => {
+                    Some((min.min(other_min), max.max(other_max)))
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// Whether this bounding box and `other` share any point. Z values are only compared if both
+    /// have them.
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        let (start, length) = self.longitude_arc();
+        let (other_start, other_length) = other.longitude_arc();
+        let longitudes =
+            offset(start, other_start) <= length || offset(other_start, start) <= other_length;
+        let z = match (self.z, other.z) {
+            (Some((min, max)), Some((other_min, other_max))) => {
+                min <= other_max && other_min <= max
+            }
+            _ => true,
+        };
+        longitudes && self.south <= other.north && other.south <= self.north && z
+    }
+
+    /// Whether `other` lies entirely within this bounding box. Z values are only compared if both
+    /// have them.
+    pub fn contains(&self, other: &BoundingBox) -> bool {
+        let (start, length) = self.longitude_arc();
+        let (other_start, other_length) = other.longitude_arc();
+        let longitudes = length >= 360.0 || offset(start, other_start) + other_length <= length;
+        let z = match (self.z, other.z) {
+            (Some((min, max)), Some((other_min, other_max))) => {
+                min <= other_min && other_max <= max
+            }
+            _ => true,
+        };
+        longitudes && self.south <= other.south && other.north <= self.north && z
+    }
+
+    /// Whether the position lies within this bounding box.
+    ///
+    /// # Panics
+    ///
+    /// If the position has fewer than 2 values.
+    pub fn contains_position(&self, position: &[f64]) -> bool {
+        self.contains(&BoundingBox::from_position(position))
+    }
+
+    /// The bounding box grown by `margin` on every side, and by `margin` in Z if it has Z values.
+    ///
+    /// If the bounding box lies within the range of longitudes and latitudes, it's taken to be in
+    /// degrees: longitudes which pass the antimeridian wrap around, so the result may cross it,
+    /// and latitudes stop at the poles. Otherwise, as for projected coordinates, every side just
+    /// moves out by `margin`.
+    ///
+    /// A negative `margin` shrinks the bounding box instead. Where opposite sides would pass each
+    /// other, they both stop at the middle, so the box never turns inside out.
+    pub fn expand_by(&self, margin: f64) -> BoundingBox {
+        let z = self.z.map(|(min, max)| grow(min, max, margin));
+        let is_lon_lat = self.west.abs() <= 180.0
+            && self.east.abs() <= 180.0
+            && self.south.abs() <= 90.0
+            && self.north.abs() <= 90.0;
+        let (south, north) = grow(self.south, self.north, margin);
+        if !is_lon_lat {
+            let (west, east) = grow(self.west, self.east, margin);
+            return BoundingBox {
+                west,
+                south,
+                east,
+                north,
+                z,
+            };
+        }
+        let (start, length) = self.longitude_arc();
+        let (start, end) = grow(start, start + length, margin);
+        let (west, east) = longitudes(start, end - start);
+        BoundingBox {
+            west,
+            south: south.max(-90.0),
+            east,
+            north: north.min(90.0),
+            z,
+        }
+    }
+
+    /// The area covered by the bounding box, as a Polygon, or as a MultiPolygon split at the
+    /// antimeridian if it crosses it. Following RFC 7946, the rings are counterclockwise.
+    pub fn to_value(&self) -> Value {
+        let rectangle = |west: f64, east: f64| {
+            vec![vec![
+                vec![west, self.south],
+                vec![east, self.south],
+                vec![east, self.north],
+                vec![west, self.north],
+                vec![west, self.south],
+            ]]
+        };
+        if self.crosses_antimeridian() {
+            Value::MultiPolygon(vec![
+                rectangle(self.west, 180.0),
+                rectangle(-180.0, self.east),
+            ])
+        } else {
+            Value::Polygon(rectangle(self.west, self.east))
+        }
+    }
+
+    /// The start and length in degrees of the range of longitudes, going east.
+    fn longitude_arc(&self) -> (f64, f64) {
+        let length = self.east - self.west;
+        if length < 0.0 {
+            (self.west, length + 360.0)
+        } else {
+            (self.west, length)
+        }
+    }
+}
+
+/// How many degrees east `to` is from `from`.
+fn offset(from: f64, to: f64) -> f64 {
+    (to - from).rem_euclid(360.0)
+}
+
+/// The range from `min` to `max` grown by `margin` at both ends, or if a negative `margin` would
+/// shrink it past nothing, its middle.
+fn grow(min: f64, max: f64, margin: f64) -> (f64, f64) {
+    if max - min + 2.0 * margin < 0.0 {
+        let middle = min + (max - min) / 2.0;
+        (middle, middle)
+    } else {
+        (min - margin, max + margin)
+    }
+}
+
+/// The west and east of a range of longitudes, wrapped to lie between -180 and 180.
+fn longitudes(start: f64, length: f64) -> (f64, f64) {
+    if length >= 360.0 {
+        return (-180.0, 180.0);
+    }
+    let wrap = |longitude: f64| {
+        if longitude < -180.0 {
+            longitude + 360.0
+        } else if longitude > 180.0 {
+            longitude - 360.0
+        } else {
+            longitude
+        }
+    };
+    (wrap(start), wrap(start + length))
+}
+
+impl TryFrom<&[f64]> for BoundingBox {
+    type Error = Error;
+
+    fn try_from(bbox: &[f64]) -> Result<Self> {
+        match *bbox {
+            [west, south, east, north] => Ok(BoundingBox::new(west, south, east, north)),
+            [west, south, min_z, east, north, max_z] => {
+                Ok(BoundingBox::new_3d(west, south, min_z, east, north, max_z))
+            }
+            _ => Err(Error::InvalidBbox(format!(
+                "expected 4 or 6 values, not {}",
+                bbox.len()
+            ))),
+        }
+    }
+}
+
+impl TryFrom<&Bbox> for BoundingBox {
+    type Error = Error;
+
+    fn try_from(bbox: &Bbox) -> Result<Self> {
+        BoundingBox::try_from(bbox.as_slice())
+    }
+}
+
+impl TryFrom<Bbox> for BoundingBox {
+    type Error = Error;
+
+    fn try_from(bbox: Bbox) -> Result<Self> {
+        BoundingBox::try_from(bbox.as_slice())
+    }
+}
+
+impl From<BoundingBox> for Bbox {
+    fn from(bbox: BoundingBox) -> Self {
+        bbox.to_bbox()
+    }
+}
+
+/// The bounding box of the positions of a Polygon, or of a MultiPolygon split at the antimeridian
+/// like those from [`BoundingBox::to_value`]. It's three-dimensional if the positions have Z
+/// values, and any values after Z are ignored.
+impl TryFrom<&Value> for BoundingBox {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self> {
+        match value {
+            Value::Polygon(rings) => bounds(rings.iter().flatten()),
+            Value::MultiPolygon(polygons) if polygons.len() == 2 => {
+                let eastern = bounds(polygons[0].iter().flatten())?;
+                let western = bounds(polygons[1].iter().flatten())?;
+                if eastern.east != 180.0 || western.west != -180.0 {
+                    return Err(Error::InvalidBbox(
+                        "a MultiPolygon which isn't split at the antimeridian".to_string(),
+                    ));
+                }
+                Ok(BoundingBox {
+                    west: eastern.west,
+                    east: western.east,
+                    ..eastern.union(&western)
+                })
+            }
+            _ => Err(Error::InvalidGeometryConversion {
+                expected_type: "Polygon",
+                found_type: value.type_name(),
+            }),
+        }
+    }
+}
+
+/// The bounding box of `positions`, ignoring any values after Z, as `Extent` does.
+fn bounds<'a>(positions: impl Iterator<Item = &'a Position>) -> Result<BoundingBox> {
+    let mut extent = Extent::default();
+    positions.for_each(|position| extent.add(position));
+    match extent.to_bbox() {
+        Some(bbox) => BoundingBox::try_from(bbox),
+        None => Err(Error::InvalidBbox(
+            "a Polygon without positions".to_string(),
+        )),
+    }
+}
+
+impl TryFrom<Value> for BoundingBox {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        BoundingBox::try_from(&value)
+    }
+}
+
+impl From<&BoundingBox> for Value {
+    fn from(bbox: &BoundingBox) -> Self {
+        bbox.to_value()
+    }
+}
+
+impl From<BoundingBox> for Value {
+    fn from(bbox: BoundingBox) -> Self {
+        bbox.to_value()
+    }
+}
+
+impl Serialize for BoundingBox {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_bbox().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BoundingBox {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error as SerdeError;
+
+        let bbox = Bbox::deserialize(deserializer)?;
+        BoundingBox::try_from(bbox).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let bbox = BoundingBox::new_3d(-10.0, -5.0, 0.0, 10.0, 5.0, 100.0);
+        assert_eq!(bbox.to_bbox(), vec![-10.0, -5.0, 0.0, 10.0, 5.0, 100.0]);
+        assert_eq!(BoundingBox::try_from(bbox.to_bbox()).unwrap(), bbox);
+        assert!(matches!(
+            BoundingBox::try_from(vec![1.0, 2.0, 3.0]),
+            Err(Error::InvalidBbox(_))
+        ));
+
+        let json = serde_json::to_string(&bbox).unwrap();
+        assert_eq!(json, "[-10.0,-5.0,0.0,10.0,5.0,100.0]");
+        assert_eq!(serde_json::from_str::<BoundingBox>(&json).unwrap(), bbox);
+        assert!(serde_json::from_str::<BoundingBox>("[1, 2]").is_err());
+
+        let polygon = Value::from(bbox);
+        assert_eq!(
+            polygon,
+            Value::Polygon(vec![vec![
+                vec![-10.0, -5.0],
+                vec![10.0, -5.0],
+                vec![10.0, 5.0],
+                vec![-10.0, 5.0],
+                vec![-10.0, -5.0],
+            ]])
+        );
+        assert_eq!(
+            BoundingBox::try_from(&polygon).unwrap(),
+            BoundingBox::new(-10.0, -5.0, 10.0, 5.0)
+        );
+        assert!(BoundingBox::try_from(Value::Point(vec![1.0, 2.0])).is_err());
+        assert!(BoundingBox::try_from(Value::Polygon(vec![])).is_err());
+
+        let crossing = BoundingBox::new(170.0, -5.0, -170.0, 5.0);
+        let multi_polygon = Value::from(crossing);
+        match &multi_polygon {
+            Value::MultiPolygon(polygons) => {
+                assert_eq!(polygons[0][0][1], vec![180.0, -5.0]);
+                assert_eq!(polygons[1][0][0], vec![-180.0, -5.0]);
+            }
+            other => panic!("expected a MultiPolygon, got {:?}", other),
+        }
+        assert_eq!(BoundingBox::try_from(&multi_polygon).unwrap(), crossing);
+        let not_split = Value::MultiPolygon(vec![vec![], vec![]]);
+        assert!(BoundingBox::try_from(not_split).is_err());
+
+        // M values are ignored.
+        let polygon = Value::Polygon(vec![vec![
+            vec![0.0, 0.0, 1.0, 5.0],
+            vec![1.0, 0.0, 2.0, 6.0],
+            vec![1.0, 1.0, 3.0, 7.0],
+            vec![0.0, 0.0, 1.0, 5.0],
+        ]]);
+        assert_eq!(
+            BoundingBox::try_from(polygon).unwrap(),
+            BoundingBox::new_3d(0.0, 0.0, 1.0, 1.0, 1.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn union() {
+        let a = BoundingBox::new(0.0, 0.0, 10.0, 10.0);
+        let b = BoundingBox::new(20.0, -5.0, 30.0, 5.0);
+        assert_eq!(a.union(&b), BoundingBox::new(0.0, -5.0, 30.0, 10.0));
+
+        // Without a box which crosses the antimeridian, the union doesn't either.
+        let west = BoundingBox::new(-175.0, 0.0, -170.0, 1.0);
+        let east = BoundingBox::new(170.0, 0.0, 175.0, 1.0);
+        assert_eq!(west.union(&east), BoundingBox::new(-175.0, 0.0, 175.0, 1.0));
+
+        let crossing = BoundingBox::new(175.0, 0.0, -175.0, 1.0);
+        assert_eq!(
+            crossing.union(&east),
+            BoundingBox::new(170.0, 0.0, -175.0, 1.0)
+        );
+        assert_eq!(
+            west.union(&crossing),
+            BoundingBox::new(175.0, 0.0, -170.0, 1.0)
+        );
+        let far = BoundingBox::new(0.0, 0.0, 5.0, 1.0);
+        assert_eq!(
+            crossing.union(&far),
+            BoundingBox::new(0.0, 0.0, -175.0, 1.0)
+        );
+
+        let with_z = BoundingBox::new_3d(0.0, 0.0, -1.0, 1.0, 1.0, 1.0);
+        assert_eq!(with_z.union(&with_z.expand_by(1.0)).z, Some((-2.0, 2.0)));
+        assert_eq!(with_z.union(&a).z, None);
+    }
+
+    #[test]
+    fn intersects_and_contains() {
+        let a = BoundingBox::new(0.0, 0.0, 10.0, 10.0);
+        assert!(a.intersects(&BoundingBox::new(5.0, 5.0, 15.0, 15.0)));
+        assert!(a.intersects(&BoundingBox::new(10.0, 10.0, 15.0, 15.0)));
+        assert!(!a.intersects(&BoundingBox::new(11.0, 0.0, 15.0, 10.0)));
+        assert!(!a.intersects(&BoundingBox::new(0.0, 11.0, 10.0, 15.0)));
+        assert!(a.contains(&BoundingBox::new(1.0, 1.0, 9.0, 9.0)));
+        assert!(!a.contains(&BoundingBox::new(1.0, 1.0, 11.0, 9.0)));
+        assert!(a.contains_position(&[10.0, 0.0]));
+
+        let crossing = BoundingBox::new(170.0, -10.0, -170.0, 10.0);
+        assert!(crossing.intersects(&BoundingBox::new(-175.0, 0.0, -160.0, 1.0)));
+        assert!(crossing.intersects(&BoundingBox::new(160.0, 0.0, 175.0, 1.0)));
+        assert!(!crossing.intersects(&a));
+        assert!(crossing.contains(&BoundingBox::new(175.0, 0.0, -175.0, 1.0)));
+        assert!(crossing.contains(&BoundingBox::new(-180.0, 0.0, -175.0, 1.0)));
+        assert!(!crossing.contains(&BoundingBox::new(175.0, 0.0, -165.0, 1.0)));
+        assert!(crossing.contains_position(&[180.0, 0.0]));
+        assert!(!crossing.contains_position(&[0.0, 0.0]));
+
+        let world = BoundingBox::new(-180.0, -90.0, 180.0, 90.0);
+        assert!(world.contains(&crossing));
+        assert!(world.intersects(&crossing));
+
+        let with_z = BoundingBox::new_3d(0.0, 0.0, 0.0, 10.0, 10.0, 10.0);
+        assert!(with_z.contains_position(&[5.0, 5.0, 5.0]));
+        assert!(!with_z.contains_position(&[5.0, 5.0, 11.0]));
+        assert!(with_z.contains_position(&[5.0, 5.0]));
+    }
+
+    #[test]
+    fn expand_by() {
+        let a = BoundingBox::new(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(a.expand_by(1.0), BoundingBox::new(-1.0, -1.0, 11.0, 11.0));
+
+        let east = BoundingBox::new(170.0, 85.0, 179.0, 89.0);
+        let expanded = east.expand_by(2.0);
+        assert_eq!(expanded, BoundingBox::new(168.0, 83.0, -179.0, 90.0));
+        assert!(expanded.crosses_antimeridian());
+
+        let wide = BoundingBox::new(-179.0, 0.0, 179.0, 1.0);
+        assert_eq!(
+            wide.expand_by(1.0),
+            BoundingBox::new(-180.0, -1.0, 180.0, 2.0)
+        );
+
+        // Projected coordinates aren't wrapped or clamped.
+        let projected = BoundingBox::new(500_000.0, 4_000_000.0, 510_000.0, 4_010_000.0);
+        assert_eq!(
+            projected.expand_by(100.0),
+            BoundingBox::new(499_900.0, 3_999_900.0, 510_100.0, 4_010_100.0)
+        );
+    }
+
+    #[test]
+    fn expand_by_negative_margin() {
+        let a = BoundingBox::new_3d(0.0, 0.0, 0.0, 10.0, 4.0, 1.0);
+        assert_eq!(
+            a.expand_by(-1.0),
+            BoundingBox::new_3d(1.0, 1.0, 0.5, 9.0, 3.0, 0.5)
+        );
+        assert_eq!(
+            a.expand_by(-10.0),
+            BoundingBox::new_3d(5.0, 2.0, 0.5, 5.0, 2.0, 0.5)
+        );
+
+        // A box crossing the antimeridian shrinks towards its middle, rather than turning into
+        // one which goes the other way around.
+        let crossing = BoundingBox::new(170.0, -10.0, -170.0, 10.0);
+        assert_eq!(
+            crossing.expand_by(-5.0),
+            BoundingBox::new(175.0, -5.0, -175.0, 5.0)
+        );
+        let shrunk = crossing.expand_by(-15.0);
+        assert_eq!(shrunk, BoundingBox::new(180.0, 0.0, 180.0, 0.0));
+        assert!(!shrunk.crosses_antimeridian());
+
+        let projected = BoundingBox::new(500_000.0, 4_000_000.0, 510_000.0, 4_010_000.0);
+        assert_eq!(
+            projected.expand_by(-6_000.0),
+            BoundingBox::new(505_000.0, 4_005_000.0, 505_000.0, 4_005_000.0)
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! The representation of GeoJSON objects in formats which aren't human readable.
+//!
+//! Binary formats like bincode or postcard aren't self-describing, so they can't deserialize the
+//! JSON object layout, whose members may come in any order and whose properties can hold any JSON
+//! value. When [`Serializer::is_human_readable`](serde::Serializer::is_human_readable) is false,
+//! GeoJSON objects are instead serialized as structs with a fixed layout, in which properties and
+//! foreign members are stored as JSON text.
+use crate::feature::Id;
+use crate::flat::{FlatType, FlatValue};
+use crate::{
+    Bbox, Feature, FeatureCollection, GeoJson, Geometry, InlineGeometry, InlinePosition,
+    InlineValue, JsonObject, LineStringType, PointType, PolygonType, Value,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "GeoJson")]
+pub(crate) enum GeoJsonDef {
+    Geometry(Geometry),
+    Feature(Feature),
+    FeatureCollection(FeatureCollection),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Geometry")]
+pub(crate) struct GeometryDef {
+    bbox: Option<Bbox>,
+    #[serde(with = "ValueDef")]
+    value: Value,
+    #[serde(with = "json_text")]
+    foreign_members: Option<JsonObject>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Value")]
+enum ValueDef {
+    Point(PointType),
+    MultiPoint(Vec<PointType>),
+    LineString(LineStringType),
+    MultiLineString(Vec<LineStringType>),
+    Polygon(PolygonType),
+    MultiPolygon(Vec<PolygonType>),
+    GeometryCollection(Vec<Geometry>),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "InlineGeometry")]
+pub(crate) struct InlineGeometryDef {
+    bbox: Option<Bbox>,
+    #[serde(with = "InlineValueDef")]
+    value: InlineValue,
+    #[serde(with = "json_text")]
+    foreign_members: Option<JsonObject>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "InlineValue")]
+enum InlineValueDef {
+    Point(InlinePosition),
+    MultiPoint(Vec<InlinePosition>),
+    LineString(Vec<InlinePosition>),
+    MultiLineString(Vec<Vec<InlinePosition>>),
+    Polygon(Vec<Vec<InlinePosition>>),
+    MultiPolygon(Vec<Vec<Vec<InlinePosition>>>),
+    GeometryCollection(Vec<InlineGeometry>),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "FlatValue")]
+pub(crate) struct FlatValueDef {
+    flat_type: FlatType,
+    dimensions: usize,
+    coordinates: Vec<f64>,
+    ring_offsets: Vec<usize>,
+    polygon_offsets: Vec<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Feature")]
+pub(crate) struct FeatureDef {
+    bbox: Option<Bbox>,
+    geometry: Option<Geometry>,
+    #[serde(with = "id")]
+    id: Option<Id>,
+    #[serde(with = "json_text")]
+    properties: Option<JsonObject>,
+    #[serde(with = "json_text")]
+    foreign_members: Option<JsonObject>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "FeatureCollection")]
+pub(crate) struct FeatureCollectionDef {
+    bbox: Option<Bbox>,
+    features: Vec<Feature>,
+    #[serde(with = "json_text")]
+    foreign_members: Option<JsonObject>,
+}
+
+/// JSON objects, which may hold values of any type, are stored as JSON text.
+mod json_text {
+    use super::*;
+
+    pub(super) fn serialize<S>(
+        object: &Option<JsonObject>,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::Error;
+
+        object
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> std::result::Result<Option<JsonObject>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        Option::<String>::deserialize(deserializer)?
+            .map(|text| serde_json::from_str(&text))
+            .transpose()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Numeric ids are stored as their JSON text, so that they keep their exact value.
+mod id {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    enum IdDef {
+        String(String),
+        Number(String),
+    }
+
+    pub(super) fn serialize<S>(
+        id: &Option<Id>,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        id.as_ref()
+            .map(|id| match id {
+                Id::String(s) => IdDef::String(s.clone()),
+                Id::Number(n) => IdDef::Number(n.to_string()),
+            })
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D>(deserializer: D) -> std::result::Result<Option<Id>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        Option::<IdDef>::deserialize(deserializer)?
+            .map(|id| match id {
+                IdDef::String(s) => Ok(Id::String(s)),
+                IdDef::Number(n) => n.parse().map(Id::Number).map_err(D::Error::custom),
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::feature::Id;
+    use crate::{Feature, FeatureCollection, GeoJson, Geometry, JsonObject, JsonValue, Value};
+    use serde_json::json;
+
+    fn object(value: JsonValue) -> Option<JsonObject> {
+        match value {
+            JsonValue::Object(object) => Some(object),
+            _ => unreachable!(),
+        }
+    }
+
+    fn feature_collection() -> FeatureCollection {
+        let geometry = Geometry {
+            bbox: Some(vec![1.0, 2.0, 3.0, 4.0]),
+            value: Value::GeometryCollection(vec![
+                Geometry::new(Value::Point(vec![1.0, 2.0])),
+                Geometry::new(Value::MultiPolygon(vec![vec![vec![
+                    vec![1.0, 2.0, 5.0],
+                    vec![3.0, 2.0, 5.0],
+                    vec![3.0, 4.0, 5.0],
+                    vec![1.0, 2.0, 5.0],
+                ]]])),
+            ]),
+            foreign_members: object(json!({ "crs": null })),
+        };
+        FeatureCollection {
+            bbox: None,
+            features: vec![
+                Feature {
+                    bbox: Some(vec![1.0, 2.0, 3.0, 4.0]),
+                    geometry: Some(geometry),
+                    id: Some(Id::Number(serde_json::Number::from(u64::MAX))),
+                    properties: object(json!({ "name": "a", "nested": { "b": [1.5, null] } })),
+                    foreign_members: object(json!({ "title": "there" })),
+                },
+                Feature {
+                    id: Some(Id::String("b".to_string())),
+                    ..Feature::default()
+                },
+            ],
+            foreign_members: object(json!({ "name": "collection" })),
+        }
+    }
+
+    #[test]
+    fn bincode_roundtrip() {
+        let geojson = GeoJson::from(feature_collection());
+        let bytes = bincode::serialize(&geojson).unwrap();
+        let decoded: GeoJson = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, geojson);
+
+        let feature = &feature_collection().features[0];
+        let bytes = bincode::serialize(feature).unwrap();
+        assert_eq!(bincode::deserialize::<Feature>(&bytes).unwrap(), *feature);
+    }
+
+    #[test]
+    fn cbor_roundtrip() {
+        let geojson = GeoJson::from(feature_collection());
+        let mut bytes = vec![];
+        ciborium::ser::into_writer(&geojson, &mut bytes).unwrap();
+        let decoded: GeoJson = ciborium::de::from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, geojson);
+    }
+
+    #[test]
+    fn invalid_json_text() {
+        let feature = Feature {
+            foreign_members: Some(JsonObject::new()),
+            ..Feature::default()
+        };
+        let mut bytes = bincode::serialize(&feature).unwrap();
+        // The foreign members are the last member, stored as `Some("{}")`.
+        let len = bytes.len();
+        bytes[len - 1] = b'x';
+        assert!(bincode::deserialize::<Feature>(&bytes).is_err());
+    }
+}
@@ -5,7 +5,7 @@ use crate::geometry;
 use crate::{
     quick_collection, Feature, FeatureCollection, GeoJson, LineStringType, PointType, PolygonType,
 };
-use crate::{Error, Result};
+use crate::{Error, PositionExt, Result};
 use std::convert::{TryFrom, TryInto};
 
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
@@ -273,13 +273,14 @@ where
     }
 }
 
+/// geo-types coordinates are two-dimensional, so any Z and M values are dropped.
 fn create_geo_coordinate<T>(point_type: &PointType) -> geo_types::Coordinate<T>
 where
     T: CoordFloat,
 {
     geo_types::Coordinate {
-        x: T::from(point_type[0]).unwrap(),
-        y: T::from(point_type[1]).unwrap(),
+        x: T::from(point_type.x()).unwrap(),
+        y: T::from(point_type.y()).unwrap(),
     }
 }
 
@@ -287,10 +288,7 @@ fn create_geo_point<T>(point_type: &PointType) -> geo_types::Point<T>
 where
     T: CoordFloat,
 {
-    geo_types::Point::new(
-        T::from(point_type[0]).unwrap(),
-        T::from(point_type[1]).unwrap(),
-    )
+    geo_types::Point(create_geo_coordinate(point_type))
 }
 
 fn create_geo_line_string<T>(line_type: &LineStringType) -> geo_types::LineString<T>
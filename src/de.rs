@@ -82,7 +82,8 @@
 //!     ...
 //! }
 //! ```
-use crate::{Feature, FeatureReader, JsonValue, Result};
+use crate::borrowed_feature::BorrowedFeatures;
+use crate::{BorrowedFeature, Feature, FeatureReader, JsonValue, Result};
 
 use std::convert::{TryFrom, TryInto};
 use std::fmt::Formatter;
@@ -151,15 +152,8 @@ where
     T: Deserialize<'de>,
 {
     #[allow(deprecated)]
-    let iter = crate::FeatureIterator::new(feature_collection_reader).map(
-        |feature_value: Result<JsonValue>| {
-            let deserializer = feature_value?.into_deserializer();
-            let visitor = FeatureVisitor::new();
-            let record: T = deserializer.deserialize_map(visitor)?;
-
-            Ok(record)
-        },
-    );
+    let iter = crate::FeatureIterator::new(feature_collection_reader)
+        .map(|feature_value: Result<JsonValue>| feature_from_json_value(feature_value?));
     Ok(iter)
 }
 
@@ -286,6 +280,73 @@ where
     T: Deserialize<'de>,
 {
     let feature_value: JsonValue = serde_json::from_reader(feature_reader)?;
+    feature_from_json_value(feature_value)
+}
+
+/// Read the features of a GeoJSON FeatureCollection into [`BorrowedFeature`]s, which borrow their
+/// members from `feature_collection_str` instead of copying them.
+///
+/// Members of the FeatureCollection other than `features` are skipped.
+///
+/// # Examples
+///
+/// ```
+/// let feature_collection_str = r#"{
+///     "type": "FeatureCollection",
+///     "features": [
+///         {
+///             "type": "Feature",
+///             "geometry": { "type": "Point", "coordinates": [11.1, 22.2] },
+///             "properties": { "name": "Downtown" }
+///         },
+///         {
+///             "type": "Feature",
+///             "geometry": { "type": "Point", "coordinates": [33.3, 44.4] },
+///             "properties": { "name": "Uptown" }
+///         }
+///     ]
+/// }"#;
+///
+/// let features = geojson::de::borrowed_features_from_str(feature_collection_str).unwrap();
+/// let uptown: Vec<_> = features
+///     .iter()
+///     .filter(|feature| feature.property("name").unwrap().get() == r#""Uptown""#)
+///     .collect();
+/// assert_eq!(uptown.len(), 1);
+/// ```
+pub fn borrowed_features_from_str(
+    feature_collection_str: &str,
+) -> Result<Vec<BorrowedFeature<'_>>> {
+    let features: BorrowedFeatures = serde_json::from_str(feature_collection_str)?;
+    Ok(features.0)
+}
+
+/// Build your struct from the JSON representation of a single GeoJSON Feature, or of a single
+/// GeoJSON Geometry, which is treated like a Feature without any properties.
+pub(crate) fn feature_or_geometry_from_json_value<'de, T>(value: JsonValue) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    let is_feature = value.get("type").and_then(JsonValue::as_str) == Some("Feature");
+    if is_feature {
+        feature_from_json_value(value)
+    } else {
+        let mut feature = crate::JsonObject::new();
+        feature.insert("type".to_string(), JsonValue::from("Feature"));
+        feature.insert("geometry".to_string(), value);
+        feature.insert(
+            "properties".to_string(),
+            JsonValue::Object(Default::default()),
+        );
+        feature_from_json_value(JsonValue::Object(feature))
+    }
+}
+
+/// Build your struct from the JSON representation of a single GeoJSON Feature.
+pub(crate) fn feature_from_json_value<'de, T>(feature_value: JsonValue) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
     let deserializer = feature_value.into_deserializer();
     let visitor = FeatureVisitor::new();
     Ok(deserializer.deserialize_map(visitor)?)
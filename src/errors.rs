@@ -1,15 +1,19 @@
 //! Module for all GeoJSON-related errors
 use crate::Feature;
 use serde_json::value::Value;
+use std::fmt;
 use thiserror::Error;
 
 /// Errors which can occur when encoding, decoding, and converting GeoJSON
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("Encountered non-array value for a 'bbox' object: `{0}`")]
     BboxExpectedArray(Value),
     #[error("Encountered non-numeric value within 'bbox' array")]
     BboxExpectedNumericValues(Value),
+    #[error("Invalid bbox: {0}")]
+    InvalidBbox(String),
     #[error("Encountered a non-object type for GeoJSON: `{0}`")]
     GeoJsonExpectedObject(Value),
     /// This was previously `GeoJsonUnknownType`, but has been split for clarity
@@ -57,6 +61,126 @@ pub enum Error {
     ExpectedObjectValue(Value),
     #[error("A position must contain two or more elements, but got `{0}`")]
     PositionTooShort(usize),
+    #[error("A position must contain at most four elements (x, y, z and m), but got `{0}`")]
+    PositionTooLong(usize),
+    #[error("The maximum nesting depth of {0} was exceeded")]
+    DepthLimitExceeded(usize),
+    #[error("A geometry has more than the maximum of {0} positions")]
+    PositionLimitExceeded(usize),
+    #[error("A FeatureCollection has more than the maximum of {0} features")]
+    FeatureLimitExceeded(usize),
+    #[error("The properties of a Feature are longer than the maximum of {0} bytes")]
+    PropertySizeLimitExceeded(usize),
+    #[error("Invalid flat geometry: {0}")]
+    InvalidFlatValue(String),
+    #[error("Line {line}: {source}")]
+    Line { line: usize, source: Box<Error> },
+    /// An error which was found at a known [`Location`] in the input.
+    ///
+    /// This is only returned when asked for with
+    /// [`ParseOptions::error_locations`](crate::ParseOptions::error_locations).
+    #[error("{source} {location}")]
+    Located {
+        location: Location,
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Error::Located { location, .. } => Some(location),
+            _ => None,
+        }
+    }
+
+    /// The error, without the location it was found at.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Error, GeoJson, ParseOptions};
+    ///
+    /// let options = ParseOptions {
+    ///     error_locations: true,
+    ///     ..ParseOptions::default()
+    /// };
+    /// let geojson_str = r#"{"type": "LineString", "coordinates": [[1, 2], [3]]}"#;
+    /// let err = GeoJson::from_reader_with_options(geojson_str.as_bytes(), &options).unwrap_err();
+    /// assert_eq!(err.location().unwrap().pointer, "/coordinates/1");
+    /// assert!(matches!(err.without_location(), Error::PositionTooShort(1)));
+    ///
+    /// // Without `error_locations`, the error isn't wrapped.
+    /// let err = geojson_str.parse::<GeoJson>().unwrap_err();
+    /// assert!(matches!(err, Error::PositionTooShort(1)));
+    /// ```
+    pub fn without_location(&self) -> &Error {
+        match self {
+            Error::Located { source, .. } => source,
+            _ => self,
+        }
+    }
+
+    /// Like [`Error::without_location`], but by value.
+    pub(crate) fn into_unlocated(self) -> Error {
+        match self {
+            Error::Located { source, .. } => *source,
+            other => other,
+        }
+    }
+
+    /// Record that the error was found in the member `name` of an object.
+    pub(crate) fn in_member(self, name: &str) -> Error {
+        self.locate(|location| location.pointer.insert_str(0, &format!("/{}", name)))
+    }
+
+    /// Record that the error was found in the element `index` of an array.
+    pub(crate) fn in_element(self, index: usize) -> Error {
+        self.locate(|location| location.pointer.insert_str(0, &format!("/{}", index)))
+    }
+
+    pub(crate) fn locate(self, update: impl FnOnce(&mut Location)) -> Error {
+        let (mut location, source) = match self {
+            Error::Located { location, source } => (location, source),
+            other => (Location::default(), Box::new(other)),
+        };
+        update(&mut location);
+        Error::Located { location, source }
+    }
+}
+
+/// Where in the input an [`Error`](enum@Error) was found.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Location {
+    /// A [JSON Pointer](https://tools.ietf.org/html/rfc6901) to the offending value, like
+    /// `/features/3/geometry/coordinates/0`, which is empty for the whole document.
+    pub pointer: String,
+    /// The index of the offending Feature within the FeatureCollection read by a
+    /// [`FeatureReader`](crate::FeatureReader).
+    pub feature_index: Option<usize>,
+    /// The line of the input, starting at 1.
+    ///
+    /// For errors in an otherwise well-formed Feature read by a
+    /// [`FeatureReader`](crate::FeatureReader), this is where the Feature starts.
+    pub line: Option<usize>,
+    /// The column of the input, starting at 1.
+    pub column: Option<usize>,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let position = match (self.line, self.column) {
+            (Some(line), Some(column)) => format!("line {} column {}", line, column),
+            (Some(line), None) => format!("line {}", line),
+            _ => String::new(),
+        };
+        match (self.pointer.is_empty(), position.is_empty()) {
+            (true, _) => write!(f, "at {}", position),
+            (false, true) => write!(f, "at `{}`", self.pointer),
+            (false, false) => write!(f, "at `{}` ({})", self.pointer, position),
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -72,3 +196,66 @@ impl From<std::io::Error> for Error {
         Self::Io(error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, GeoJson, ParseOptions};
+
+    fn parse_with_locations(geojson_str: &str) -> Result<GeoJson, Error> {
+        let options = ParseOptions {
+            error_locations: true,
+            ..ParseOptions::default()
+        };
+        GeoJson::from_reader_with_options(geojson_str.as_bytes(), &options)
+    }
+
+    #[test]
+    fn nested_pointer() {
+        let geojson_str = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                { "type": "Feature", "geometry": null, "properties": null },
+                { "type": "Feature", "properties": null, "geometry": {
+                    "type": "GeometryCollection",
+                    "geometries": [
+                        { "type": "Point", "coordinates": [1, 2] },
+                        { "type": "LineString", "coordinates": [[1, 2], [3, "4"]] }
+                    ]
+                } }
+            ]
+        }"#;
+        let err = parse_with_locations(geojson_str).unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!(
+            location.pointer,
+            "/features/1/geometry/geometries/1/coordinates/1/1"
+        );
+        assert!(matches!(err.without_location(), Error::ExpectedF64Value));
+
+        // Other ways of parsing don't wrap the error.
+        assert!(matches!(
+            geojson_str.parse::<GeoJson>(),
+            Err(Error::ExpectedF64Value)
+        ));
+        let json_value: serde_json::Value = serde_json::from_str(geojson_str).unwrap();
+        assert!(matches!(
+            GeoJson::from_json_value(json_value),
+            Err(Error::ExpectedF64Value)
+        ));
+        let err = serde_json::from_str::<GeoJson>(geojson_str).unwrap_err();
+        assert!(!err.to_string().contains("/features/1"));
+    }
+
+    #[test]
+    fn type_errors_belong_to_the_object() {
+        let err = parse_with_locations(
+            r#"{"type": "Feature", "geometry": {"type": "Pointy", "coordinates": []}}"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.location().unwrap().pointer, "/geometry");
+        assert!(matches!(
+            err.without_location(),
+            Error::GeometryUnknownType(_)
+        ));
+    }
+}
@@ -15,8 +15,12 @@
 use std::convert::TryFrom;
 use std::str::FromStr;
 
+use crate::compact::FeatureDef;
 use crate::errors::{Error, Result};
-use crate::{util, Feature, Geometry, Value};
+use crate::object_serializer::{serialize_object, Member};
+use crate::object_visitor::GeoJsonObject;
+use crate::util::Extent;
+use crate::{util, Bbox, Feature, Geometry, Value, WithOptions, WriteOptions};
 use crate::{JsonObject, JsonValue};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::json;
@@ -145,6 +149,43 @@ impl Feature {
             Some(props) => Box::new(props.iter()),
         }
     }
+
+    /// Computes the bbox of the feature's geometry from its coordinates, ignoring any `bbox`s
+    /// present, or `None` if it has no geometry.
+    ///
+    /// See [`Geometry::compute_bbox`].
+    pub fn compute_bbox(&self) -> Option<Bbox> {
+        self.extent().to_bbox()
+    }
+
+    /// Sets `bbox` to the [computed bbox](Feature::compute_bbox).
+    pub fn with_computed_bbox(mut self) -> Self {
+        self.bbox = self.compute_bbox();
+        self
+    }
+
+    /// Sets `bbox` to the [computed bbox](Feature::compute_bbox), and does the same for the
+    /// geometry with [`Geometry::fill_bboxes`].
+    pub fn fill_bboxes(&mut self) {
+        self.fill_extent();
+    }
+
+    pub(crate) fn extent(&self) -> Extent {
+        self.geometry
+            .as_ref()
+            .map(Geometry::extent)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn fill_extent(&mut self) -> Extent {
+        let extent = self
+            .geometry
+            .as_mut()
+            .map(Geometry::fill_extent)
+            .unwrap_or_default();
+        self.bbox = extent.to_bbox();
+        extent
+    }
 }
 
 impl TryFrom<JsonObject> for Feature {
@@ -182,7 +223,44 @@ impl Serialize for Feature {
     where
         S: Serializer,
     {
-        JsonObject::from(self).serialize(serializer)
+        if !serializer.is_human_readable() {
+            return FeatureDef::serialize(self, serializer);
+        }
+        WithOptions::new(self, &WriteOptions::default()).serialize(serializer)
+    }
+}
+
+impl Serialize for WithOptions<'_, Feature> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return self.value.serialize(serializer);
+        }
+        let feature = self.value;
+        let bbox = feature
+            .bbox
+            .as_ref()
+            .map(|bbox| ("bbox", Member::Bbox(bbox)));
+        let id = feature.id.as_ref().map(|id| ("id", Member::Id(id)));
+        let members = bbox
+            .into_iter()
+            .chain([("geometry", Member::Geometry(feature.geometry.as_ref()))])
+            .chain(id)
+            .chain([
+                (
+                    "properties",
+                    Member::Properties(feature.properties.as_ref()),
+                ),
+                ("type", Member::Type("Feature")),
+            ]);
+        serialize_object(
+            serializer,
+            members,
+            feature.foreign_members.as_ref(),
+            self.options,
+        )
     }
 }
 
@@ -193,9 +271,13 @@ impl<'de> Deserialize<'de> for Feature {
     {
         use serde::de::Error as SerdeError;
 
-        let val = JsonObject::deserialize(deserializer)?;
+        if !deserializer.is_human_readable() {
+            return FeatureDef::deserialize(deserializer);
+        }
 
-        Feature::from_json_object(val).map_err(|e| D::Error::custom(e.to_string()))
+        GeoJsonObject::deserialize(deserializer)?
+            .into_feature()
+            .map_err(|e| D::Error::custom(e.without_location()))
     }
 }
 
@@ -16,8 +16,12 @@ use std::convert::TryFrom;
 use std::iter::FromIterator;
 use std::str::FromStr;
 
+use crate::compact::FeatureCollectionDef;
 use crate::errors::{Error, Result};
-use crate::{util, Bbox, Feature};
+use crate::object_serializer::{serialize_object, Member};
+use crate::object_visitor::GeoJsonObject;
+use crate::util::Extent;
+use crate::{util, Bbox, BoundingBox, Feature, WithOptions, WriteOptions};
 use crate::{JsonObject, JsonValue};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::json;
@@ -123,6 +127,38 @@ impl FeatureCollection {
     pub fn from_json_value(value: JsonValue) -> Result<Self> {
         Self::try_from(value)
     }
+
+    /// Computes the bbox of the geometries of every feature from their coordinates, ignoring
+    /// any `bbox`s present, or `None` if they have no positions.
+    ///
+    /// See [`Geometry::compute_bbox`](crate::Geometry::compute_bbox).
+    pub fn compute_bbox(&self) -> Option<Bbox> {
+        self.extent().to_bbox()
+    }
+
+    /// Sets `bbox` to the [computed bbox](FeatureCollection::compute_bbox).
+    pub fn with_computed_bbox(mut self) -> Self {
+        self.bbox = self.compute_bbox();
+        self
+    }
+
+    /// Sets `bbox` to the [computed bbox](FeatureCollection::compute_bbox), and does the same for
+    /// every feature with [`Feature::fill_bboxes`].
+    pub fn fill_bboxes(&mut self) {
+        let mut extent = Extent::default();
+        for feature in &mut self.features {
+            extent.union(&feature.fill_extent());
+        }
+        self.bbox = extent.to_bbox();
+    }
+
+    fn extent(&self) -> Extent {
+        let mut extent = Extent::default();
+        for feature in &self.features {
+            extent.union(&feature.extent());
+        }
+        extent
+    }
 }
 
 impl TryFrom<JsonObject> for FeatureCollection {
@@ -168,7 +204,36 @@ impl Serialize for FeatureCollection {
     where
         S: Serializer,
     {
-        JsonObject::from(self).serialize(serializer)
+        if !serializer.is_human_readable() {
+            return FeatureCollectionDef::serialize(self, serializer);
+        }
+        WithOptions::new(self, &WriteOptions::default()).serialize(serializer)
+    }
+}
+
+impl Serialize for WithOptions<'_, FeatureCollection> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return self.value.serialize(serializer);
+        }
+        let collection = self.value;
+        let bbox = collection
+            .bbox
+            .as_ref()
+            .map(|bbox| ("bbox", Member::Bbox(bbox)));
+        let members = bbox.into_iter().chain([
+            ("features", Member::Features(&collection.features)),
+            ("type", Member::Type("FeatureCollection")),
+        ]);
+        serialize_object(
+            serializer,
+            members,
+            collection.foreign_members.as_ref(),
+            self.options,
+        )
     }
 }
 
@@ -179,68 +244,43 @@ impl<'de> Deserialize<'de> for FeatureCollection {
     {
         use serde::de::Error as SerdeError;
 
-        let val = JsonObject::deserialize(deserializer)?;
+        if !deserializer.is_human_readable() {
+            return FeatureCollectionDef::deserialize(deserializer);
+        }
 
-        FeatureCollection::from_json_object(val).map_err(|e| D::Error::custom(e.to_string()))
+        GeoJsonObject::deserialize(deserializer)?
+            .into_feature_collection()
+            .map_err(|e| D::Error::custom(e.without_location()))
     }
 }
 
 /// Create a [`FeatureCollection`] using the [`collect`]
 /// method on an iterator of `Feature`s. If every item
 /// contains a bounding-box of the same dimension, then the
-/// output has a bounding-box of the union of them.
-/// Otherwise, the output will not have a bounding-box.
+/// output has a bounding-box of the union of them (see
+/// [`BoundingBox::union`]). Otherwise, or if there are no
+/// items, the output will not have a bounding-box.
 ///
 /// [`collect`]: std::iter::Iterator::collect
 impl FromIterator<Feature> for FeatureCollection {
     fn from_iter<T: IntoIterator<Item = Feature>>(iter: T) -> Self {
-        let mut bbox = Some(vec![]);
+        let mut bbox: Option<Bbox> = None;
+        let mut has_bbox = true;
 
         let features = iter
             .into_iter()
             .inspect(|feat| {
-                // Try to compute the bounding-box
-
-                let (curr_bbox, curr_len) = match &mut bbox {
-                    Some(curr_bbox) => {
-                        let curr_len = curr_bbox.len();
-                        (curr_bbox, curr_len)
-                    }
-                    None => {
-                        // implies we can't compute a
-                        // bounding-box for this collection
-                        return;
-                    }
-                };
-
-                match &feat.bbox {
-                    None => {
-                        bbox = None;
-                    }
-                    Some(fbox) if fbox.is_empty() || fbox.len() % 2 != 0 => {
-                        bbox = None;
-                    }
-                    Some(fbox) if curr_len == 0 => {
-                        // First iteration: just copy values from fbox
-                        *curr_bbox = fbox.clone();
-                    }
-                    Some(fbox) if curr_len != fbox.len() => {
-                        bbox = None;
-                    }
-                    Some(fbox) => {
-                        // Update bbox by computing min/max
-                        curr_bbox.iter_mut().zip(fbox.iter()).enumerate().for_each(
-                            |(idx, (bc, fc))| {
-                                if idx < curr_len / 2 {
-                                    // These are the min coords
-                                    *bc = fc.min(*bc);
-                                } else {
-                                    *bc = fc.max(*bc);
-                                }
-                            },
-                        );
-                    }
+                if !has_bbox {
+                    // A previous feature had no usable bounding-box
+                    return;
+                }
+                bbox = match (&bbox, &feat.bbox) {
+                    (_, Some(fbox)) if fbox.is_empty() || fbox.len() % 2 != 0 => None,
+                    (None, Some(fbox)) => Some(fbox.clone()),
+                    (Some(curr_bbox), Some(fbox)) => union_bboxes(curr_bbox, fbox),
+                    (_, None) => None,
                 };
+                has_bbox = bbox.is_some();
             })
             .collect();
         FeatureCollection {
@@ -251,6 +291,28 @@ impl FromIterator<Feature> for FeatureCollection {
     }
 }
 
+/// The smallest bbox which contains both `a` and `b`, or `None` if they have different lengths.
+///
+/// Bboxes which a [`BoundingBox`] can hold are joined with [`BoundingBox::union`], which accounts
+/// for the antimeridian. Any others, e.g. with M values, are joined dimension by dimension.
+fn union_bboxes(a: &Bbox, b: &Bbox) -> Option<Bbox> {
+    if a.len() != b.len() {
+        return None;
+    }
+    if let (Ok(a), Ok(b)) = (BoundingBox::try_from(a), BoundingBox::try_from(b)) {
+        return Some(a.union(&b).into());
+    }
+    let dimensions = a.len() / 2;
+    let union = a.iter().zip(b).enumerate().map(|(index, (&a, &b))| {
+        if index < dimensions {
+            a.min(b)
+        } else {
+            a.max(b)
+        }
+    });
+    Some(union.collect())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Error, Feature, FeatureCollection, Value};
@@ -279,6 +341,76 @@ mod tests {
         assert_eq!(fc.bbox, Some(vec![-1., -1., -1., 11., 11., 11.]));
     }
 
+    #[test]
+    fn test_fc_compute_bbox() {
+        let mut fc = FeatureCollection {
+            bbox: None,
+            features: vec![
+                Feature::from(Value::Point(vec![1., 2.])),
+                Feature {
+                    bbox: Some(vec![0., 0., 0., 0.]),
+                    ..Feature::default()
+                },
+                Feature::from(Value::LineString(vec![vec![-1., 3.], vec![0., 0.]])),
+            ],
+            foreign_members: None,
+        };
+        assert_eq!(fc.compute_bbox(), Some(vec![-1., 0., 1., 3.]));
+        assert_eq!(fc.features[1].compute_bbox(), None);
+
+        fc.fill_bboxes();
+        assert_eq!(fc.bbox, Some(vec![-1., 0., 1., 3.]));
+        assert_eq!(fc.features[0].bbox, Some(vec![1., 2., 1., 2.]));
+        assert_eq!(fc.features[1].bbox, None);
+        let geometry = fc.features[2].geometry.as_ref().unwrap();
+        assert_eq!(geometry.bbox, Some(vec![-1., 0., 0., 3.]));
+
+        let empty: FeatureCollection = vec![].into_iter().collect();
+        assert_eq!(empty.with_computed_bbox().bbox, None);
+    }
+
+    #[test]
+    fn test_fc_from_iterator_without_bbox() {
+        let feature = |bbox: Option<Vec<f64>>| Feature {
+            bbox,
+            ..Feature::from(Value::Point(vec![0., 0.]))
+        };
+
+        let fc: FeatureCollection = vec![].into_iter().collect();
+        assert_eq!(fc.bbox, None);
+
+        let fc: FeatureCollection = vec![feature(Some(vec![0., 0., 1., 1.])), feature(None)]
+            .into_iter()
+            .collect();
+        assert_eq!(fc.bbox, None);
+
+        let fc: FeatureCollection = vec![
+            feature(Some(vec![0., 0., 1., 1.])),
+            feature(Some(vec![0., 0., 0., 1., 1., 1.])),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(fc.bbox, None);
+
+        // Bounding-boxes which cross the antimeridian are joined around it.
+        let fc: FeatureCollection = vec![
+            feature(Some(vec![170., 0., -170., 1.])),
+            feature(Some(vec![-175., -1., -165., 0.])),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(fc.bbox, Some(vec![170., -1., -165., 1.]));
+
+        // Bounding-boxes which `BoundingBox` can't hold are joined dimension by dimension.
+        let fc: FeatureCollection = vec![
+            feature(Some(vec![0., 0., 0., 0., 1., 1., 1., 1.])),
+            feature(Some(vec![-1., 2., 0., 5., 0., 3., 1., 6.])),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(fc.bbox, Some(vec![-1., 0., 0., 0., 1., 3., 1., 6.]));
+    }
+
     fn feature_collection_json() -> String {
         json!({ "type": "FeatureCollection", "features": [
         {
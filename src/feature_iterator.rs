@@ -13,10 +13,11 @@
 // limitations under the License.
 #![allow(deprecated)]
 
-use crate::{Feature, Result};
+use crate::parse_options::LimitScanner;
+use crate::{util, Error, Feature, FeatureCollectionMembers, JsonValue, ParseOptions, Result};
 
 use serde::Deserialize;
-use std::io;
+use std::io::{self, Read};
 use std::marker::PhantomData;
 
 // TODO: Eventually make this private - and expose only FeatureReader.
@@ -30,12 +31,26 @@ use std::marker::PhantomData;
 /// This has the benefit of not having to wait until the end of the
 /// stream to get results, and avoids having to allocate memory for the complete collection.
 ///
+/// The top-level object is tokenized as it is read, so the `features` member may appear at any
+/// position, and any other members (e.g. `bbox` or foreign members) are skipped over correctly.
+///
 /// Based on example code found at <https://github.com/serde-rs/serde/issues/903#issuecomment-297488118>.
 ///
 /// [GeoJSON Format Specification § 3.3](https://datatracker.ietf.org/doc/html/rfc7946#section-3.3)
 pub struct FeatureIterator<'de, R, D = Feature> {
-    reader: R,
+    reader: PositionReader<R>,
     state: State,
+    members: FeatureCollectionMembers,
+    /// The number of features which have been started.
+    feature_count: usize,
+    /// The line and column of the first byte of the current feature.
+    feature_start: (usize, usize),
+    /// Whether to carry on with the next feature after one which can't be read.
+    skip_invalid: bool,
+    /// Whether to wrap errors in `Error::Located`.
+    error_locations: bool,
+    /// A byte which was consumed from the reader but not yet used.
+    pending_byte: Option<u8>,
     output: PhantomData<D>,
     lifetime: PhantomData<&'de ()>,
 }
@@ -43,6 +58,7 @@ pub struct FeatureIterator<'de, R, D = Feature> {
 #[derive(Debug, Copy, Clone)]
 enum State {
     BeforeFeatures,
+    StartOfFeatures,
     DuringFeatures,
     AfterFeatures,
 }
@@ -50,55 +66,404 @@ enum State {
 impl<'de, R, D> FeatureIterator<'de, R, D> {
     pub fn new(reader: R) -> Self {
         FeatureIterator {
-            reader,
+            reader: PositionReader {
+                reader,
+                line: 1,
+                column: 0,
+                limits: None,
+            },
             state: State::BeforeFeatures,
+            members: FeatureCollectionMembers::default(),
+            feature_count: 0,
+            feature_start: (1, 0),
+            skip_invalid: false,
+            error_locations: false,
+            pending_byte: None,
             output: PhantomData,
             lifetime: PhantomData,
         }
     }
+
+    /// The members of the FeatureCollection, other than `type` and `features`, which have been
+    /// read so far.
+    pub(crate) fn members(&self) -> &FeatureCollectionMembers {
+        &self.members
+    }
+
+    /// Check the input against the limits of `options`.
+    pub(crate) fn set_options(&mut self, options: ParseOptions) {
+        self.error_locations = options.error_locations;
+        self.reader.limits = Some(LimitScanner::new(options));
+    }
+
+    /// The error for a limit which was exceeded, which is what any other error was caused by.
+    fn limit_error(&self) -> Option<Error> {
+        self.reader.limits.as_ref().and_then(LimitScanner::error)
+    }
+
+    /// Carry on with the next feature after one which can't be read, see
+    /// [`FeatureReader::skip_invalid_features`](crate::FeatureReader::skip_invalid_features).
+    pub(crate) fn skip_invalid_features(&mut self) {
+        self.skip_invalid = true;
+    }
+
+    /// Handle a feature which was read, but couldn't be converted. Like a syntax error, this
+    /// ends the features, unless invalid features are being skipped.
+    pub(crate) fn invalid_feature(&mut self) {
+        if !self.skip_invalid {
+            self.state = State::AfterFeatures;
+        }
+    }
+
+    /// Record that `err` was found at the current position of the input, if error locations were
+    /// asked for.
+    pub(crate) fn locate_here(&self, err: Error) -> Error {
+        let err = self.limit_error().unwrap_or(err);
+        if !self.error_locations {
+            return err.into_unlocated();
+        }
+        let (line, column) = (self.reader.line, self.reader.column);
+        err.locate(|location| {
+            location.line = Some(line);
+            location.column = Some(column);
+        })
+    }
+
+    /// Record that `err` was found in the feature which was read last, if error locations were
+    /// asked for.
+    pub(crate) fn locate_feature_error(&self, err: Error) -> Error {
+        if !self.error_locations {
+            return self.limit_error().unwrap_or_else(|| err.into_unlocated());
+        }
+        let index = self.feature_count.saturating_sub(1);
+        let (err, (line, column)) = match self.limit_error() {
+            Some(limit_err) => (limit_err, (self.reader.line, self.reader.column)),
+            None => {
+                let position = feature_error_position(&err, self.feature_start);
+                (err, position)
+            }
+        };
+        err.in_element(index)
+            .in_member("features")
+            .locate(|location| {
+                location.feature_index = Some(index);
+                location.line = Some(line);
+                location.column = Some(column);
+            })
+    }
+}
+
+/// The line and column at which `err` was found in a feature starting at `feature_start`.
+///
+/// JSON syntax errors are located where they were found, any others where the feature starts.
+fn feature_error_position(err: &Error, feature_start: (usize, usize)) -> (usize, usize) {
+    let (start_line, start_column) = feature_start;
+    match err {
+        // The position is relative to the start of the feature.
+        Error::MalformedJson(json_err) if json_err.line() == 1 => (
+            start_line,
+            start_column + json_err.column().saturating_sub(1),
+        ),
+        Error::MalformedJson(json_err) if json_err.line() > 1 => {
+            (start_line + json_err.line() - 1, json_err.column())
+        }
+        _ => (start_line, start_column),
+    }
 }
 
 impl<'de, R, D> FeatureIterator<'de, R, D>
 where
     R: io::Read,
 {
-    fn seek_to_next_feature(&mut self) -> Result<bool> {
-        let mut next_bytes = [0];
-        loop {
-            self.reader.read_exact(&mut next_bytes)?;
-            let next_byte = next_bytes[0] as char;
-            if next_byte.is_whitespace() {
-                continue;
+    /// Read the top-level object up to the opening bracket of the `features` array, if that
+    /// hasn't happened yet.
+    pub(crate) fn read_header(&mut self) -> Result<()> {
+        if let State::BeforeFeatures = self.state {
+            self.state = State::AfterFeatures;
+            self.expect_byte(b'{')?;
+            if !self.read_members()? {
+                return Err(Error::ExpectedProperty("features".to_string()));
             }
+            self.state = State::StartOfFeatures;
+        }
+        Ok(())
+    }
 
-            match (self.state, next_byte) {
-                (State::BeforeFeatures, '[') => {
-                    self.state = State::DuringFeatures;
-                    return Ok(true);
+    /// Deserialize the next element of the `features` array, or return `None` once the array
+    /// (and the rest of the enclosing object) has been consumed.
+    pub(crate) fn next_feature<T>(&mut self) -> Option<Result<T>>
+    where
+        T: Deserialize<'de>,
+    {
+        let first_byte = match self.seek_to_next_feature() {
+            Ok(Some(first_byte)) => first_byte,
+            Ok(None) => return None,
+            Err(err) => {
+                // The stream can't be resynchronized, so don't try to read any further.
+                self.state = State::AfterFeatures;
+                return Some(Err(self.locate_here(err)));
+            }
+        };
+        self.feature_count += 1;
+        self.feature_start = (self.reader.line, self.reader.column);
+
+        if self.skip_invalid {
+            return Some(self.next_feature_value(first_byte));
+        }
+
+        // The first byte of the feature has already been consumed while looking for it.
+        let first_byte = [first_byte];
+        let reader = io::Read::chain(&first_byte[..], &mut self.reader);
+        let mut de = serde_json::Deserializer::from_reader(reader);
+        match T::deserialize(&mut de) {
+            Ok(v) => Some(Ok(v)),
+            Err(err) => {
+                self.state = State::AfterFeatures;
+                Some(Err(self.locate_feature_error(err.into())))
+            }
+        }
+    }
+
+    /// Read the raw bytes of the feature starting with `first_byte` before deserializing them,
+    /// so that the stream can carry on after it even if it's invalid.
+    fn next_feature_value<T>(&mut self, first_byte: u8) -> Result<T>
+    where
+        T: Deserialize<'de>,
+    {
+        let mut buf = vec![];
+        match self.read_value(first_byte, &mut buf) {
+            Ok(terminator) => self.pending_byte = terminator,
+            Err(err) => {
+                // The end of the feature wasn't found, so there's nothing to carry on with.
+                self.state = State::AfterFeatures;
+                return Err(self.locate_here(err));
+            }
+        }
+        let mut de = serde_json::Deserializer::from_reader(buf.as_slice());
+        T::deserialize(&mut de)
+            .and_then(|value| de.end().map(|()| value))
+            .map_err(|err| self.locate_feature_error(err.into()))
+    }
+
+    /// Advance to the start of the next element of the `features` array, returning its first
+    /// byte, or `None` once the array (and the rest of the enclosing object) has been consumed.
+    fn seek_to_next_feature(&mut self) -> Result<Option<u8>> {
+        match self.state {
+            State::BeforeFeatures => {
+                self.read_header()?;
+                self.seek_to_next_feature()
+            }
+            State::StartOfFeatures => match self.next_non_whitespace()? {
+                b']' => {
+                    self.finish_features()?;
+                    Ok(None)
                 }
-                (State::BeforeFeatures, _) => {
-                    continue;
+                first_byte => {
+                    self.state = State::DuringFeatures;
+                    Ok(Some(first_byte))
                 }
-                (State::DuringFeatures, ',') => {
-                    return Ok(true);
+            },
+            State::DuringFeatures => match self.next_non_whitespace()? {
+                b',' => Ok(Some(self.next_non_whitespace()?)),
+                b']' => {
+                    self.finish_features()?;
+                    Ok(None)
                 }
-                (State::DuringFeatures, ']') => {
-                    self.state = State::AfterFeatures;
-                    return Ok(false);
+                other => Err(unexpected_byte("`,` or `]`", other)),
+            },
+            State::AfterFeatures => Ok(None),
+        }
+    }
+
+    /// Consume the members following the `features` array, up to the closing brace of the
+    /// FeatureCollection.
+    fn finish_features(&mut self) -> Result<()> {
+        self.state = State::AfterFeatures;
+        match self.next_non_whitespace()? {
+            b',' => {
+                if self.read_members()? {
+                    return Err(invalid_input("duplicate `features` member"));
                 }
-                (State::AfterFeatures, _) => {
-                    unreachable!("should not seek if we've already finished processing features")
+                Ok(())
+            }
+            b'}' => Ok(()),
+            other => Err(unexpected_byte("`,` or `}`", other)),
+        }
+    }
+
+    /// Read `"key": value` pairs of the top-level object until either the value of the
+    /// `features` member is reached, in which case the opening `[` has been consumed and `true`
+    /// is returned, or the end of the object is reached, in which case `false` is returned.
+    fn read_members(&mut self) -> Result<bool> {
+        let mut next_byte = self.next_non_whitespace()?;
+        if next_byte == b'}' {
+            return Ok(false);
+        }
+        loop {
+            if next_byte != b'"' {
+                return Err(unexpected_byte("a member name", next_byte));
+            }
+            let mut key_bytes = vec![b'"'];
+            self.read_string(&mut key_bytes)?;
+            let key: String = serde_json::from_slice(&key_bytes)?;
+            self.expect_byte(b':')?;
+
+            let first_byte = self.next_non_whitespace()?;
+            if key == "features" && first_byte == b'[' {
+                return Ok(true);
+            }
+
+            let mut value_bytes = vec![];
+            let terminator = self.read_value(first_byte, &mut value_bytes)?;
+            let value: JsonValue = serde_json::from_slice(&value_bytes)?;
+            read_member(&mut self.members, key.clone(), value).map_err(|e| e.in_member(&key))?;
+
+            next_byte = match terminator {
+                Some(byte) if !byte.is_ascii_whitespace() => byte,
+                _ => self.next_non_whitespace()?,
+            };
+            match next_byte {
+                b',' => next_byte = self.next_non_whitespace()?,
+                b'}' => return Ok(false),
+                other => return Err(unexpected_byte("`,` or `}`", other)),
+            }
+        }
+    }
+
+    /// Copy the raw bytes of a single JSON value, which starts with `first_byte`, into `buf`.
+    ///
+    /// Numbers and literals have no closing delimiter, so the byte which terminated them is
+    /// returned, since it has already been consumed from the reader.
+    fn read_value(&mut self, first_byte: u8, buf: &mut Vec<u8>) -> Result<Option<u8>> {
+        buf.push(first_byte);
+        match first_byte {
+            b'"' => {
+                self.read_string(buf)?;
+                Ok(None)
+            }
+            b'{' | b'[' => {
+                let mut depth = 1;
+                while depth > 0 {
+                    let byte = self.next_byte()?;
+                    buf.push(byte);
+                    match byte {
+                        b'"' => self.read_string(buf)?,
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => depth -= 1,
+                        _ => {}
+                    }
                 }
-                _ => {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidInput,
-                        format!("next byte: {}", next_byte),
-                    )
-                    .into());
+                Ok(None)
+            }
+            _ => loop {
+                let byte = self.next_byte()?;
+                if byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'-' | b'.') {
+                    buf.push(byte);
+                } else {
+                    return Ok(Some(byte));
                 }
+            },
+        }
+    }
+
+    /// Copy the remainder of a JSON string, whose opening quote has already been consumed, into
+    /// `buf`.
+    fn read_string(&mut self, buf: &mut Vec<u8>) -> Result<()> {
+        loop {
+            let byte = self.next_byte()?;
+            buf.push(byte);
+            match byte {
+                b'\\' => buf.push(self.next_byte()?),
+                b'"' => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    fn expect_byte(&mut self, expected: u8) -> Result<()> {
+        match self.next_non_whitespace()? {
+            byte if byte == expected => Ok(()),
+            other => Err(unexpected_byte(&format!("`{}`", expected as char), other)),
+        }
+    }
+
+    fn next_non_whitespace(&mut self) -> Result<u8> {
+        loop {
+            let byte = self.next_byte()?;
+            if !byte.is_ascii_whitespace() {
+                return Ok(byte);
             }
         }
     }
+
+    fn next_byte(&mut self) -> Result<u8> {
+        if let Some(byte) = self.pending_byte.take() {
+            return Ok(byte);
+        }
+        let mut next_bytes = [0];
+        self.reader.read_exact(&mut next_bytes)?;
+        Ok(next_bytes[0])
+    }
+}
+
+/// Handle a member of the top-level object, other than an array-valued `features` member.
+pub(crate) fn read_member(
+    members: &mut FeatureCollectionMembers,
+    key: String,
+    value: JsonValue,
+) -> Result<()> {
+    if key == "features" {
+        // We already know it's not an array, but this gives us a consistent error.
+        util::expect_owned_array(value)?;
+    } else if key == "type" {
+        if value.as_str() != Some("FeatureCollection") {
+            return Err(Error::ExpectedType {
+                expected: "FeatureCollection".to_string(),
+                actual: value.as_str().unwrap_or("").to_string(),
+            });
+        }
+    } else {
+        members.insert(key, value)?;
+    }
+    Ok(())
+}
+
+/// Keeps track of the line and column of the last byte which was read, and checks the input
+/// against any limits.
+struct PositionReader<R> {
+    reader: R,
+    line: usize,
+    column: usize,
+    limits: Option<LimitScanner>,
+}
+
+impl<R: io::Read> io::Read for PositionReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.reader.read(buf)?;
+        if let Some(limits) = &mut self.limits {
+            limits.scan(&buf[..len])?;
+        }
+        for &byte in &buf[..len] {
+            if byte == b'\n' {
+                self.line += 1;
+                self.column = 0;
+            } else {
+                self.column += 1;
+            }
+        }
+        Ok(len)
+    }
+}
+
+pub(crate) fn invalid_input(message: &str) -> Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message).into()
+}
+
+pub(crate) fn unexpected_byte(expected: &str, found: u8) -> Error {
+    invalid_input(&format!(
+        "expected {} but found `{}`",
+        expected, found as char
+    ))
 }
 
 impl<'de, R, D> Iterator for FeatureIterator<'de, R, D>
@@ -109,20 +474,7 @@ where
     type Item = Result<D>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.seek_to_next_feature() {
-            Ok(true) => {}
-            Ok(false) => return None,
-            Err(err) => {
-                return Some(Err(err));
-            }
-        }
-
-        let de = serde_json::Deserializer::from_reader(&mut self.reader);
-        match de.into_iter().next() {
-            Some(Ok(v)) => Some(Ok(v)),
-            Some(Err(err)) => Some(Err(err.into())),
-            None => None,
-        }
+        self.next_feature()
     }
 }
 
@@ -230,8 +582,8 @@ mod tests {
         fn type_field_before_features_field() {
             let type_first = r#"
               {
-                type: "FeatureCollection",
-                features: [
+                "type": "FeatureCollection",
+                "features": [
                   {
                     "type": "Feature",
                     "geometry": {
@@ -262,7 +614,7 @@ mod tests {
         fn features_field_before_type_field() {
             let type_first = r#"
               {
-                features: [
+                "features": [
                   {
                     "type": "Feature",
                     "geometry": {
@@ -280,7 +632,7 @@ mod tests {
                     "properties": { }
                   }
                 ],
-                type: "FeatureCollection"
+                "type": "FeatureCollection"
               }
             "#;
             let features: Vec<Feature> =
@@ -289,5 +641,99 @@ mod tests {
                     .collect();
             assert_eq!(features.len(), 2);
         }
+
+        #[test]
+        fn bbox_before_features_field() {
+            let bbox_first = r#"
+              {
+                "bbox": [1.1, 1.2, 2.1, 2.2],
+                "type": "FeatureCollection",
+                "features": [
+                  {
+                    "type": "Feature",
+                    "geometry": {
+                      "type": "Point",
+                      "coordinates": [1.1, 1.2]
+                    },
+                    "properties": {}
+                  },
+                  {
+                    "type": "Feature",
+                    "geometry": {
+                      "type": "Point",
+                      "coordinates": [2.1, 2.2]
+                    },
+                    "properties": {}
+                  }
+                ]
+              }
+            "#;
+            let features: Vec<Feature> =
+                FeatureIterator::new(BufReader::new(bbox_first.as_bytes()))
+                    .map(Result::unwrap)
+                    .collect();
+            assert_eq!(features.len(), 2);
+            assert_eq!(
+                features[0].geometry.as_ref().unwrap().value,
+                Value::Point(vec![1.1, 1.2])
+            );
+        }
+
+        #[test]
+        fn foreign_members_before_and_after_features_field() {
+            let foreign_members = r#"
+              {
+                "name": "tricky [ \"string\" ] {",
+                "count": 2,
+                "nested": { "features": [1, 2], "other": [[], {}] },
+                "type": "FeatureCollection",
+                "features": [
+                  {
+                    "type": "Feature",
+                    "geometry": {
+                      "type": "Point",
+                      "coordinates": [1.1, 1.2]
+                    },
+                    "properties": {}
+                  }
+                ],
+                "trailing": null,
+                "total":2.5e3}
+            "#;
+            let features: Vec<Feature> =
+                FeatureIterator::new(BufReader::new(foreign_members.as_bytes()))
+                    .map(Result::unwrap)
+                    .collect();
+            assert_eq!(features.len(), 1);
+        }
+
+        #[test]
+        fn empty_features() {
+            let empty = r#"{ "type": "FeatureCollection", "features": [ ] }"#;
+            let mut fi = FeatureIterator::<_, Feature>::new(BufReader::new(empty.as_bytes()));
+            assert!(fi.next().is_none());
+        }
+
+        #[test]
+        fn missing_features_field() {
+            let missing = r#"{ "type": "FeatureCollection", "bbox": [1, 2, 3, 4] }"#;
+            let mut fi = FeatureIterator::<_, Feature>::new(BufReader::new(missing.as_bytes()));
+            assert!(matches!(
+                fi.next(),
+                Some(Err(crate::Error::ExpectedProperty(_)))
+            ));
+            assert!(fi.next().is_none());
+        }
+
+        #[test]
+        fn unexpected_type() {
+            let not_a_collection = r#"{ "type": "Feature", "features": [] }"#;
+            let mut fi =
+                FeatureIterator::<_, Feature>::new(BufReader::new(not_a_collection.as_bytes()));
+            assert!(matches!(
+                fi.next(),
+                Some(Err(crate::Error::ExpectedType { .. }))
+            ));
+        }
     }
 }
@@ -0,0 +1,231 @@
+use crate::de::feature_or_geometry_from_json_value;
+use crate::{util, Error, Feature, JsonValue, Result};
+
+use serde::de::DeserializeOwned;
+
+use std::io::{self, BufRead, BufReader, Read};
+
+/// Enumerates individual Features from newline-delimited GeoJSON, also known as GeoJSONL or
+/// ndjson, which holds one Feature per line.
+///
+/// Lines holding a bare Geometry are read as a Feature with that geometry, and blank lines are
+/// skipped.
+///
+/// Errors for a particular line are reported as [`Error::Line`], along with its (1-based) line
+/// number. Because every line stands on its own, reading continues with the next line after
+/// such an error.
+pub struct FeatureLinesReader<R> {
+    reader: BufReader<R>,
+}
+
+impl<R: Read> FeatureLinesReader<R> {
+    /// Create a FeatureLinesReader from the given `reader`.
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+        }
+    }
+
+    /// Iterate over the individual [`Feature`s](Feature), one per line.
+    ///
+    /// If instead you'd like to deserialize directly to your own struct, see
+    /// [`FeatureLinesReader::deserialize`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lines = r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[125.6,10.1]},"properties":{"name":"Dinagat Islands"}}
+    /// {"type":"Feature","geometry":{"type":"Point","coordinates":[2.3,4.5]},"properties":{"name":"Neverland"}}
+    /// "#;
+    ///
+    /// use geojson::FeatureLinesReader;
+    /// let feature_reader = FeatureLinesReader::from_reader(lines.as_bytes());
+    /// let names: Vec<String> = feature_reader
+    ///     .features()
+    ///     .map(|feature| {
+    ///         let feature = feature.expect("valid geojson feature");
+    ///         feature.property("name").unwrap().as_str().unwrap().to_string()
+    ///     })
+    ///     .collect();
+    /// assert_eq!(names, vec!["Dinagat Islands", "Neverland"]);
+    /// ```
+    pub fn features(self) -> impl Iterator<Item = Result<Feature>> {
+        self.lines().map(|line| {
+            let (line, value) = line?;
+            util::feature_from_feature_or_geometry(value).map_err(|err| at_line(line, err))
+        })
+    }
+
+    /// Deserialize the features, one per line, into your own custom struct using the
+    /// [`serde`](../../serde) crate.
+    ///
+    /// See [`FeatureReader::deserialize`](crate::FeatureReader::deserialize) for the
+    /// requirements on your struct.
+    ///
+    /// # Examples
+    #[cfg_attr(feature = "geo-types", doc = "```")]
+    #[cfg_attr(not(feature = "geo-types"), doc = "```ignore")]
+    /// use geojson::{FeatureLinesReader, de::deserialize_geometry};
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct MyStruct {
+    ///     #[serde(deserialize_with = "deserialize_geometry")]
+    ///     geometry: geo_types::Point<f64>,
+    ///     name: String,
+    /// }
+    ///
+    /// let lines = r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[125.6,10.1]},"properties":{"name":"Dinagat Islands"}}"#;
+    ///
+    /// let feature_reader = FeatureLinesReader::from_reader(lines.as_bytes());
+    /// for my_struct in feature_reader.deserialize::<MyStruct>().unwrap() {
+    ///     let my_struct = my_struct.expect("valid geojson feature");
+    ///     assert_eq!(my_struct.name, "Dinagat Islands");
+    ///     assert_eq!(my_struct.geometry.x(), 125.6);
+    /// }
+    /// ```
+    pub fn deserialize<D: DeserializeOwned>(self) -> Result<impl Iterator<Item = Result<D>>> {
+        Ok(self.lines().map(|line| {
+            let (line, value) = line?;
+            feature_or_geometry_from_json_value(value).map_err(|err| at_line(line, err))
+        }))
+    }
+
+    /// Parse each non-blank line as JSON, along with its line number.
+    fn lines(mut self) -> impl Iterator<Item = Result<(usize, JsonValue)>> {
+        let mut buf = Vec::new();
+        let mut line = 0;
+        std::iter::from_fn(move || loop {
+            buf.clear();
+            match self.reader.read_until(b'\n', &mut buf) {
+                Ok(0) => return None,
+                Ok(_) => line += 1,
+                Err(err) => return Some(Err(err.into())),
+            }
+            // The line has been consumed even if it isn't valid UTF-8, so its error still gets
+            // its line number.
+            let text = match std::str::from_utf8(&buf) {
+                Ok(text) => text.trim(),
+                Err(err) => {
+                    let err = io::Error::new(io::ErrorKind::InvalidData, err);
+                    return Some(Err(at_line(line, err.into())));
+                }
+            };
+            if text.is_empty() {
+                continue;
+            }
+            return Some(
+                serde_json::from_str(text)
+                    .map(|value| (line, value))
+                    .map_err(|err| at_line(line, err.into())),
+            );
+        })
+    }
+}
+
+fn at_line(line: usize, error: Error) -> Error {
+    Error::Line {
+        line,
+        source: Box::new(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Geometry, Value};
+
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct MyRecord {
+        geometry: crate::Geometry,
+        name: String,
+        age: u64,
+    }
+
+    fn lines() -> &'static str {
+        concat!(
+            r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[125.6,10.1]},"properties":{"name":"Dinagat Islands","age":123}}"#,
+            "\r\n\n",
+            r#"{"type":"Point","coordinates":[7.8,9.0]}"#,
+            "\n",
+            r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[2.3,4.5]},"properties":{"name":"Neverland","age":456}}"#,
+        )
+    }
+
+    #[test]
+    fn read_features() {
+        let features: Vec<Feature> = FeatureLinesReader::from_reader(lines().as_bytes())
+            .features()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(features.len(), 3);
+        assert_eq!(features[0].property("age").unwrap(), 123);
+        assert_eq!(features[1], Feature::from(Value::Point(vec![7.8, 9.0])));
+        assert_eq!(features[2].property("name").unwrap(), "Neverland");
+    }
+
+    #[test]
+    fn deserialize_into_type() {
+        let input = lines().replace(r#"{"type":"Point","coordinates":[7.8,9.0]}"#, "");
+        let records: Vec<MyRecord> = FeatureLinesReader::from_reader(input.as_bytes())
+            .deserialize()
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name, "Dinagat Islands");
+        assert_eq!(records[1].age, 456);
+        assert_eq!(
+            records[1].geometry,
+            Geometry::new(Value::Point(vec![2.3, 4.5]))
+        );
+    }
+
+    #[test]
+    fn errors_report_line_number() {
+        let input = lines().replace(
+            r#"{"type":"Point","coordinates":[7.8,9.0]}"#,
+            r#"{"type":"Point","coordinates":[7.8]}"#,
+        );
+        let results: Vec<Result<Feature>> = FeatureLinesReader::from_reader(input.as_bytes())
+            .features()
+            .collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        match &results[1] {
+            Err(Error::Line { line, source }) => {
+                assert_eq!(*line, 3);
+                assert!(matches!(**source, Error::PositionTooShort(1)));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(results[2].is_ok());
+
+        let mut results = FeatureLinesReader::from_reader("\n{\"type\":".as_bytes()).features();
+        let err = results.next().unwrap().unwrap_err();
+        assert!(err.to_string().starts_with("Line 2: "));
+        assert!(results.next().is_none());
+    }
+
+    #[test]
+    fn invalid_utf8_reports_line_number() {
+        let mut input = lines().as_bytes().to_vec();
+        let first_line_end = input.iter().position(|&byte| byte == b'\n').unwrap();
+        input.splice(0..first_line_end, b"\xff".iter().copied());
+        let results: Vec<Result<Feature>> = FeatureLinesReader::from_reader(input.as_slice())
+            .features()
+            .collect();
+        assert_eq!(results.len(), 3);
+        match &results[0] {
+            Err(Error::Line { line, source }) => {
+                assert_eq!(*line, 1);
+                assert!(matches!(**source, Error::Io(_)));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        // Later lines keep their numbers.
+        assert!(results[1].is_ok());
+        assert!(results[2].is_ok());
+    }
+}
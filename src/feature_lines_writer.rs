@@ -0,0 +1,168 @@
+use crate::ser::to_feature_writer;
+use crate::{Feature, Result};
+
+use serde::Serialize;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// Write Features as newline-delimited GeoJSON, also known as GeoJSONL or ndjson, which holds one
+/// Feature per line.
+///
+/// Unlike [`FeatureWriter`](crate::FeatureWriter), there's no enclosing FeatureCollection to close,
+/// so you can append more features to an existing file at any time, see
+/// [`FeatureLinesWriter::append_to`].
+pub struct FeatureLinesWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> FeatureLinesWriter<W> {
+    /// Create a FeatureLinesWriter from the given `writer`.
+    ///
+    /// To append features from your custom structs, use [`FeatureLinesWriter::serialize`].
+    ///
+    /// To append features from [`Feature`] use [`FeatureLinesWriter::write_feature`].
+    pub fn from_writer(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Write a [`crate::Feature`] struct to the output stream. If you'd like to
+    /// serialize your own custom structs, see [`FeatureLinesWriter::serialize`] instead.
+    pub fn write_feature(&mut self, feature: &Feature) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, feature)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    /// Serialize your own custom struct as a line holding a Feature using the [`serde`] crate.
+    ///
+    /// See [`FeatureWriter::serialize`](crate::FeatureWriter::serialize) for the requirements
+    /// on your struct.
+    pub fn serialize<S: Serialize>(&mut self, value: &S) -> Result<()> {
+        to_feature_writer(&mut self.writer, value)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    /// Flush the underlying writer buffer.
+    pub fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+impl<W: Read + Write + Seek> FeatureLinesWriter<W> {
+    /// Create a FeatureLinesWriter which appends to the existing content of `writer`.
+    ///
+    /// If the existing content doesn't end with a newline, one is written first, so that the
+    /// first appended Feature starts on its own line.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use geojson::{Feature, FeatureLinesWriter, Value};
+    /// use std::fs::OpenOptions;
+    ///
+    /// let file = OpenOptions::new()
+    ///     .read(true)
+    ///     .append(true)
+    ///     .create(true)
+    ///     .open("features.geojsonl")
+    ///     .unwrap();
+    ///
+    /// let mut writer = FeatureLinesWriter::append_to(file).unwrap();
+    /// writer
+    ///     .write_feature(&Feature::from(Value::Point(vec![1.0, 2.0])))
+    ///     .unwrap();
+    /// ```
+    pub fn append_to(mut writer: W) -> Result<Self> {
+        let len = writer.seek(SeekFrom::End(0))?;
+        if len > 0 {
+            let mut last_byte = [0];
+            writer.seek(SeekFrom::End(-1))?;
+            writer.read_exact(&mut last_byte)?;
+            if last_byte[0] != b'\n' {
+                writer.write_all(b"\n")?;
+            }
+        }
+        Ok(Self::from_writer(writer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FeatureLinesReader, Geometry, Value};
+
+    use std::io::Cursor;
+
+    #[derive(Serialize)]
+    struct MyRecord {
+        geometry: Geometry,
+        name: String,
+        age: u64,
+    }
+
+    fn feature(name: &str) -> Feature {
+        let mut feature = Feature::from(Value::Point(vec![1.1, 1.2]));
+        feature.set_property("name", name);
+        feature
+    }
+
+    #[test]
+    fn write_feature() {
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let mut writer = FeatureLinesWriter::from_writer(&mut buffer);
+            writer.write_feature(&feature("Mishka")).unwrap();
+            writer.write_feature(&feature("Jane")).unwrap();
+        }
+
+        let expected = format!(
+            "{}\n{}\n",
+            serde_json::to_string(&feature("Mishka")).unwrap(),
+            serde_json::to_string(&feature("Jane")).unwrap()
+        );
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+    }
+
+    #[test]
+    fn serialize() {
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let mut writer = FeatureLinesWriter::from_writer(&mut buffer);
+            writer
+                .serialize(&MyRecord {
+                    geometry: Geometry::from(Value::Point(vec![1.1, 1.2])),
+                    name: "Mishka".to_string(),
+                    age: 12,
+                })
+                .unwrap();
+        }
+
+        let features: Vec<Feature> = FeatureLinesReader::from_reader(buffer.as_slice())
+            .features()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0].property("name").unwrap(), "Mishka");
+        assert_eq!(features[0].property("age").unwrap(), 12);
+    }
+
+    #[test]
+    fn append_to_existing_content() {
+        let existing = serde_json::to_string(&feature("Mishka")).unwrap();
+        let mut cursor = Cursor::new(existing.into_bytes());
+        {
+            let mut writer = FeatureLinesWriter::append_to(&mut cursor).unwrap();
+            writer.write_feature(&feature("Jane")).unwrap();
+        }
+        {
+            let mut writer = FeatureLinesWriter::append_to(&mut cursor).unwrap();
+            writer.write_feature(&feature("Fido")).unwrap();
+        }
+
+        let names: Vec<String> = FeatureLinesReader::from_reader(cursor.get_ref().as_slice())
+            .features()
+            .map(|feature| feature.unwrap().property("name").unwrap().to_string())
+            .collect();
+        assert_eq!(names, vec!["\"Mishka\"", "\"Jane\"", "\"Fido\""]);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::de::feature_from_json_value;
+use crate::feature_splitter::FeatureSplitter;
+use crate::{Feature, FeatureCollectionMembers, JsonValue, Result};
+
+use serde::de::DeserializeOwned;
+
+/// Incrementally parse the Features of a GeoJSON FeatureCollection from chunks of bytes, for
+/// when there's no [`std::io::Read`] to pull them from.
+///
+/// Hand each chunk of input to [`FeatureParser::feed`], in order, to get back the features it
+/// completed, then call [`FeatureParser::finish`] once the input has ended. Features may be split
+/// across chunks at any byte.
+///
+/// The results are the same as those of [`FeatureReader`](crate::FeatureReader): any error ends
+/// the input, since it can't be resynchronized afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::FeatureParser;
+///
+/// let feature_collection_string = r#"{
+///      "type": "FeatureCollection",
+///      "features": [
+///          {
+///            "type": "Feature",
+///            "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+///            "properties": { "name": "Dinagat Islands" }
+///          },
+///          {
+///            "type": "Feature",
+///            "geometry": { "type": "Point", "coordinates": [2.3, 4.5] },
+///            "properties": { "name": "Neverland" }
+///          }
+///      ]
+/// }"#;
+///
+/// let mut parser = FeatureParser::new();
+/// let mut names = vec![];
+/// for chunk in feature_collection_string.as_bytes().chunks(64) {
+///     for feature in parser.feed(chunk) {
+///         let feature = feature.expect("valid geojson feature");
+///         names.push(feature.property("name").unwrap().as_str().unwrap().to_string());
+///     }
+/// }
+/// parser.finish().expect("complete FeatureCollection");
+/// assert_eq!(names, vec!["Dinagat Islands", "Neverland"]);
+/// ```
+pub struct FeatureParser<D = Feature> {
+    splitter: FeatureSplitter,
+    parse: fn(&[u8]) -> Result<D>,
+}
+
+impl FeatureParser<Feature> {
+    /// Create a FeatureParser which produces [`Feature`s](Feature).
+    ///
+    /// If instead you'd like to deserialize directly to your own struct, see
+    /// [`FeatureParser::deserialize`].
+    pub fn new() -> Self {
+        Self::with_parse(|bytes| Ok(serde_json::from_slice(bytes)?))
+    }
+}
+
+impl Default for FeatureParser<Feature> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: DeserializeOwned> FeatureParser<D> {
+    /// Create a FeatureParser which deserializes each feature into your own custom struct
+    /// using the [`serde`](../../serde) crate.
+    ///
+    /// See [`FeatureReader::deserialize`](crate::FeatureReader::deserialize) for the
+    /// requirements on your struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::FeatureParser;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct MyStruct {
+    ///     geometry: geojson::Geometry,
+    ///     name: String,
+    /// }
+    ///
+    /// let mut parser = FeatureParser::<MyStruct>::deserialize();
+    /// let mut my_structs = parser.feed(br#"{"type": "FeatureCollection", "features": [{"type": "Feature", "#);
+    /// assert!(my_structs.is_empty());
+    /// my_structs.extend(parser.feed(br#""geometry": {"type": "Point", "coordinates": [2.3, 4.5]}, "properties": {"name": "Neverland"}}]}"#));
+    /// parser.finish().unwrap();
+    ///
+    /// let my_struct = my_structs.pop().unwrap().unwrap();
+    /// assert_eq!(my_struct.name, "Neverland");
+    /// ```
+    pub fn deserialize() -> Self {
+        Self::with_parse(|bytes| {
+            let feature_value: JsonValue = serde_json::from_slice(bytes)?;
+            feature_from_json_value(feature_value)
+        })
+    }
+}
+
+impl<D> FeatureParser<D> {
+    fn with_parse(parse: fn(&[u8]) -> Result<D>) -> Self {
+        FeatureParser {
+            splitter: FeatureSplitter::new(),
+            parse,
+        }
+    }
+
+    /// Consume the next chunk of input, returning the features completed by it.
+    ///
+    /// If an error is encountered, it is the last element, and all further input is rejected.
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<Result<D>> {
+        let mut feature_bytes = vec![];
+        let split = self.splitter.feed(bytes, &mut feature_bytes);
+
+        let mut features = Vec::with_capacity(feature_bytes.len() + 1);
+        for bytes in feature_bytes {
+            let feature = (self.parse)(&bytes);
+            let failed = feature.is_err();
+            features.push(feature);
+            if failed {
+                self.splitter.fail();
+                return features;
+            }
+        }
+        if let Err(err) = split {
+            features.push(Err(err));
+        }
+        features
+    }
+
+    /// Signal the end of input, failing if the FeatureCollection is incomplete.
+    pub fn finish(&mut self) -> Result<()> {
+        self.splitter.finish()
+    }
+
+    /// The `bbox` and foreign members of the FeatureCollection which have been read so far.
+    ///
+    /// Members which precede the `features` array are available once the first feature has
+    /// been produced, and members which follow it once the parser has finished.
+    pub fn collection_members(&self) -> &FeatureCollectionMembers {
+        self.splitter.members()
+    }
+
+    /// Whether an error has been produced, after which all further input is rejected.
+    pub fn is_failed(&self) -> bool {
+        self.splitter.is_failed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FeatureReader;
+
+    fn fc() -> &'static str {
+        r#"{
+            "type": "FeatureCollection",
+            "bbox": [2.3, 4.5, 125.6, 10.1],
+            "features": [
+                {
+                  "type": "Feature",
+                  "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+                  "properties": { "name": "Dinagat Islands" }
+                },
+                {
+                  "type": "Feature",
+                  "geometry": { "type": "Point", "coordinates": [2.3, 4.5] },
+                  "properties": { "name": "Neverland" }
+                }
+            ],
+            "name": "islands"
+        }"#
+    }
+
+    #[test]
+    fn same_as_feature_reader() {
+        let expected: Vec<Feature> = FeatureReader::from_reader(fc().as_bytes())
+            .features()
+            .map(Result::unwrap)
+            .collect();
+
+        for size in [1, 2, 3, 17, 1000] {
+            let mut parser = FeatureParser::new();
+            let mut features = vec![];
+            for chunk in fc().as_bytes().chunks(size) {
+                features.extend(parser.feed(chunk).into_iter().map(Result::unwrap));
+            }
+            parser.finish().unwrap();
+            assert_eq!(features, expected);
+            assert_eq!(
+                parser.collection_members().bbox,
+                Some(vec![2.3, 4.5, 125.6, 10.1])
+            );
+            assert_eq!(
+                parser
+                    .collection_members()
+                    .foreign_members
+                    .as_ref()
+                    .unwrap()
+                    .get("name")
+                    .unwrap(),
+                "islands"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_feature_ends_input() {
+        let input = fc().replace("[2.3, 4.5] }", "[2.3] }");
+        let mut parser = FeatureParser::new();
+        let results = parser.feed(input.as_bytes());
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(parser.is_failed());
+        assert!(parser.feed(b" ")[0].is_err());
+        assert!(parser.finish().is_err());
+    }
+
+    #[test]
+    fn incomplete_input() {
+        let input = &fc()[..fc().find("Neverland").unwrap()];
+        let mut parser = FeatureParser::new();
+        let results = parser.feed(input.as_bytes());
+        assert_eq!(results.len(), 1);
+        assert!(!parser.is_failed());
+        assert!(parser.finish().is_err());
+    }
+}
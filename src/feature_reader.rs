@@ -1,5 +1,8 @@
-use crate::de::deserialize_feature_collection;
-use crate::{Feature, Result};
+use crate::de::feature_from_json_value;
+use crate::object_visitor::GeoJsonObject;
+#[allow(deprecated)]
+use crate::FeatureIterator;
+use crate::{util, Bbox, Feature, JsonObject, JsonValue, LazyFeature, ParseOptions, Result};
 
 use serde::de::DeserializeOwned;
 
@@ -7,13 +10,148 @@ use std::io::Read;
 
 /// Enumerates individual Features from a GeoJSON FeatureCollection
 pub struct FeatureReader<R> {
-    reader: R,
+    #[allow(deprecated)]
+    iter: FeatureIterator<'static, R>,
 }
 
 impl<R: Read> FeatureReader<R> {
     /// Create a FeatureReader from the given `reader`.
     pub fn from_reader(reader: R) -> Self {
-        Self { reader }
+        #[allow(deprecated)]
+        let iter = FeatureIterator::new(reader);
+        Self { iter }
+    }
+
+    /// Carry on reading after a feature which can't be read, rather than ending the iteration.
+    ///
+    /// By default, the first invalid feature is the last item returned by [`features`],
+    /// [`lazy_features`] or [`deserialize`]. With this set, the error is returned and iteration
+    /// resumes at the next element of the `features` array. With
+    /// [`ParseOptions::error_locations`] set, [`Error::location`] gives the index of the feature
+    /// the error was found in.
+    ///
+    /// To find where each feature ends, its JSON text is buffered before it's parsed. A feature
+    /// whose brackets or string quotes don't balance can't be skipped, and still ends the
+    /// iteration, as does any error outside of the features.
+    ///
+    /// [`features`]: FeatureReader::features
+    /// [`lazy_features`]: FeatureReader::lazy_features
+    /// [`deserialize`]: FeatureReader::deserialize
+    /// [`Error::location`]: crate::Error::location
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let feature_collection_string = r#"{
+    ///      "type": "FeatureCollection",
+    ///      "features": [
+    ///          { "type": "Feature", "geometry": null, "properties": { "name": "a" } },
+    ///          { "type": "Feature", "geometry": null, "properties": { "name": b } },
+    ///          { "type": "Feature", "geometry": { "type": "Point" }, "properties": null },
+    ///          { "type": "Feature", "geometry": null, "properties": { "name": "d" } }
+    ///      ]
+    /// }"#
+    /// .as_bytes();
+    ///
+    /// let options = geojson::ParseOptions {
+    ///     error_locations: true,
+    ///     ..geojson::ParseOptions::default()
+    /// };
+    /// let features = geojson::FeatureReader::from_reader(feature_collection_string)
+    ///     .with_options(options)
+    ///     .skip_invalid_features()
+    ///     .features();
+    /// let mut read = vec![];
+    /// let mut invalid = vec![];
+    /// for feature in features {
+    ///     match feature {
+    ///         Ok(feature) => read.push(feature.property("name").unwrap().clone()),
+    ///         Err(err) => invalid.push(err.location().unwrap().feature_index.unwrap()),
+    ///     }
+    /// }
+    /// assert_eq!(read, ["a", "d"]);
+    /// assert_eq!(invalid, [1, 2]);
+    /// ```
+    pub fn skip_invalid_features(mut self) -> Self {
+        self.iter.skip_invalid_features();
+        self
+    }
+
+    /// Reject input which exceeds the limits of `options`, and report where errors were found if
+    /// they ask for it.
+    ///
+    /// Exceeding a limit ends the iteration, even with
+    /// [`skip_invalid_features`](FeatureReader::skip_invalid_features).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Error, FeatureReader, ParseOptions};
+    ///
+    /// let feature_collection_string = r#"{
+    ///      "type": "FeatureCollection",
+    ///      "features": [
+    ///          { "type": "Feature", "geometry": null, "properties": null },
+    ///          { "type": "Feature", "geometry": null, "properties": null }
+    ///      ]
+    /// }"#
+    /// .as_bytes();
+    ///
+    /// let options = ParseOptions {
+    ///     max_features: Some(1),
+    ///     ..ParseOptions::default()
+    /// };
+    /// let mut features = FeatureReader::from_reader(feature_collection_string)
+    ///     .with_options(options)
+    ///     .features();
+    /// assert!(features.next().unwrap().is_ok());
+    /// let err = features.next().unwrap().unwrap_err();
+    /// assert!(matches!(err, Error::FeatureLimitExceeded(1)));
+    /// assert!(features.next().is_none());
+    /// ```
+    pub fn with_options(mut self, options: ParseOptions) -> Self {
+        self.iter.set_options(options);
+        self
+    }
+
+    /// Read the FeatureCollection up to its `features` member, returning the members which
+    /// preceded it, like `bbox` or any foreign members.
+    ///
+    /// Members which follow the `features` array are only available once all of the features
+    /// have been read, see [`Features::collection_members`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let feature_collection_string = r#"{
+    ///      "type": "FeatureCollection",
+    ///      "bbox": [100.0, 0.0, 105.0, 1.0],
+    ///      "name": "my collection",
+    ///      "features": [
+    ///          {
+    ///            "type": "Feature",
+    ///            "geometry": { "type": "Point", "coordinates": [102.0, 0.5] },
+    ///            "properties": { "name": "Dinagat Islands" }
+    ///          }
+    ///      ]
+    /// }"#
+    /// .as_bytes();
+    ///
+    /// let mut feature_reader = geojson::FeatureReader::from_reader(feature_collection_string);
+    /// let members = feature_reader.collection_members().unwrap();
+    /// assert_eq!(members.bbox, Some(vec![100.0, 0.0, 105.0, 1.0]));
+    /// assert_eq!(
+    ///     members.foreign_members.as_ref().unwrap()["name"],
+    ///     "my collection"
+    /// );
+    ///
+    /// assert_eq!(feature_reader.features().count(), 1);
+    /// ```
+    pub fn collection_members(&mut self) -> Result<&FeatureCollectionMembers> {
+        if let Err(err) = self.iter.read_header() {
+            return Err(self.iter.locate_here(err));
+        }
+        Ok(self.iter.members())
     }
 
     /// Iterate over the individual [`Feature`s](Feature) of a FeatureCollection.
@@ -64,9 +202,57 @@ impl<R: Read> FeatureReader<R> {
     ///     }
     /// }
     /// ```
-    pub fn features(self) -> impl Iterator<Item = Result<Feature>> {
-        #[allow(deprecated)]
-        crate::FeatureIterator::new(self.reader)
+    pub fn features(self) -> Features<R> {
+        Features {
+            iter: self.iter,
+            read: |iter| {
+                let object = iter.next_feature::<GeoJsonObject>()?;
+                Some(object.and_then(|object| {
+                    object.into_feature().map_err(|err| {
+                        iter.invalid_feature();
+                        iter.locate_feature_error(err)
+                    })
+                }))
+            },
+        }
+    }
+
+    /// Iterate over the individual features of a FeatureCollection as [`LazyFeature`]s, which
+    /// only parse their geometry and properties when they're accessed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let feature_collection_string = r#"{
+    ///      "type": "FeatureCollection",
+    ///      "features": [
+    ///          {
+    ///            "type": "Feature",
+    ///            "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+    ///            "properties": { "name": "Dinagat Islands", "age": 123 }
+    ///          },
+    ///          {
+    ///            "type": "Feature",
+    ///            "geometry": { "type": "Point", "coordinates": [2.3, 4.5] },
+    ///            "properties": { "name": "Neverland", "age": 456 }
+    ///          }
+    ///      ]
+    /// }"#
+    /// .as_bytes();
+    ///
+    /// let feature_reader = geojson::FeatureReader::from_reader(feature_collection_string);
+    /// let mut total_age = 0;
+    /// for feature in feature_reader.lazy_features() {
+    ///     let feature = feature.expect("valid geojson feature");
+    ///     total_age += feature.property_as::<u64>("age").unwrap().unwrap();
+    /// }
+    /// assert_eq!(total_age, 579);
+    /// ```
+    pub fn lazy_features(self) -> Features<R, LazyFeature> {
+        Features {
+            iter: self.iter,
+            read: |iter| iter.next_feature(),
+        }
     }
 
     /// Deserialize the features of FeatureCollection into your own custom
@@ -151,8 +337,105 @@ impl<R: Read> FeatureReader<R> {
     ///     age: u64,
     /// }
     /// ```
-    pub fn deserialize<D: DeserializeOwned>(self) -> Result<impl Iterator<Item = Result<D>>> {
-        deserialize_feature_collection(self.reader)
+    ///
+    /// As with [`FeatureReader::features`], the members of the FeatureCollection are available
+    /// from [`Features::collection_members`].
+    pub fn deserialize<D: DeserializeOwned>(self) -> Result<Features<R, D>> {
+        Ok(Features {
+            iter: self.iter,
+            read: |iter| {
+                let feature_value = iter.next_feature::<JsonValue>()?;
+                Some(feature_value.and_then(|feature_value| {
+                    feature_from_json_value(feature_value)
+                        .map_err(|err| iter.locate_feature_error(err))
+                }))
+            },
+        })
+    }
+}
+
+/// An iterator over the [`Feature`]s of a FeatureCollection, created by
+/// [`FeatureReader::features`], over its [`LazyFeature`]s, created by
+/// [`FeatureReader::lazy_features`], or over your own structs, created by
+/// [`FeatureReader::deserialize`].
+pub struct Features<R, F = Feature> {
+    #[allow(deprecated)]
+    iter: FeatureIterator<'static, R>,
+    #[allow(deprecated)]
+    read: fn(&mut FeatureIterator<'static, R>) -> Option<Result<F>>,
+}
+
+impl<R, F> Features<R, F> {
+    /// The members of the FeatureCollection other than `type` and `features`.
+    ///
+    /// Members which precede the `features` array are available as soon as the first feature has
+    /// been read. Members which follow it are only available once the iterator has returned
+    /// `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let feature_collection_string = r#"{
+    ///      "type": "FeatureCollection",
+    ///      "features": [
+    ///          {
+    ///            "type": "Feature",
+    ///            "geometry": { "type": "Point", "coordinates": [102.0, 0.5] },
+    ///            "properties": { "name": "Dinagat Islands" }
+    ///          }
+    ///      ],
+    ///      "bbox": [102.0, 0.5, 102.0, 0.5]
+    /// }"#
+    /// .as_bytes();
+    ///
+    /// let mut features = geojson::FeatureReader::from_reader(feature_collection_string).features();
+    /// for feature in &mut features {
+    ///     let feature = feature.expect("valid geojson feature");
+    /// }
+    /// assert_eq!(
+    ///     features.collection_members().bbox,
+    ///     Some(vec![102.0, 0.5, 102.0, 0.5])
+    /// );
+    /// ```
+    pub fn collection_members(&self) -> &FeatureCollectionMembers {
+        self.iter.members()
+    }
+}
+
+impl<R: Read, F> Iterator for Features<R, F> {
+    type Item = Result<F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.read)(&mut self.iter)
+    }
+}
+
+/// The members of a FeatureCollection read by a [`FeatureReader`], other than its `type` and
+/// `features`.
+///
+/// [GeoJSON Format Specification § 3.3](https://tools.ietf.org/html/rfc7946#section-3.3)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeatureCollectionMembers {
+    /// Bounding Box
+    ///
+    /// [GeoJSON Format Specification § 5](https://tools.ietf.org/html/rfc7946#section-5)
+    pub bbox: Option<Bbox>,
+    /// Foreign Members
+    ///
+    /// [GeoJSON Format Specification § 6](https://tools.ietf.org/html/rfc7946#section-6)
+    pub foreign_members: Option<JsonObject>,
+}
+
+impl FeatureCollectionMembers {
+    pub(crate) fn insert(&mut self, key: String, value: JsonValue) -> Result<()> {
+        if key == "bbox" {
+            self.bbox = Some(util::json_to_bbox(value)?);
+        } else {
+            self.foreign_members
+                .get_or_insert_with(JsonObject::new)
+                .insert(key, value);
+        }
+        Ok(())
     }
 }
 
@@ -230,4 +513,214 @@ mod tests {
         assert_eq!(records[1].name, "Neverland");
         assert_eq!(records[1].age, 456);
     }
+
+    #[test]
+    fn collection_members() {
+        // `json!` would sort the keys, so spell out the order explicitly.
+        let feature_collection_string = r#"{
+            "bbox": [2.3, 4.5, 125.6, 10.1],
+            "type": "FeatureCollection",
+            "name": "places",
+            "features": [
+                {
+                  "type": "Feature",
+                  "geometry": {
+                    "type": "Point",
+                    "coordinates": [125.6, 10.1]
+                  },
+                  "properties": {
+                    "name": "Dinagat Islands",
+                    "age": 123
+                  }
+                }
+            ],
+            "crs": { "type": "name", "properties": { "name": "EPSG:4326" } }
+        }"#;
+
+        let mut feature_reader = FeatureReader::from_reader(feature_collection_string.as_bytes());
+        let header = feature_reader.collection_members().unwrap().clone();
+        assert_eq!(header.bbox, Some(vec![2.3, 4.5, 125.6, 10.1]));
+        let header_foreign_members = header.foreign_members.unwrap();
+        assert_eq!(header_foreign_members.len(), 1);
+        assert_eq!(header_foreign_members["name"], "places");
+
+        let mut features = feature_reader.features();
+        assert_eq!(
+            features.next().unwrap().unwrap().property("age").unwrap(),
+            123
+        );
+        assert!(features.next().is_none());
+
+        let members = features.collection_members();
+        assert_eq!(members.bbox, Some(vec![2.3, 4.5, 125.6, 10.1]));
+        let foreign_members = members.foreign_members.as_ref().unwrap();
+        assert_eq!(foreign_members.len(), 2);
+        assert_eq!(foreign_members["crs"]["properties"]["name"], "EPSG:4326");
+
+        // The same members are available when deserializing to a custom struct.
+        let mut records = FeatureReader::from_reader(feature_collection_string.as_bytes())
+            .deserialize::<MyRecord>()
+            .unwrap();
+        assert_eq!(records.next().unwrap().unwrap().age, 123);
+        assert!(records.next().is_none());
+        let foreign_members = records.collection_members().foreign_members.as_ref();
+        assert_eq!(foreign_members.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn deserialize_after_collection_members() {
+        let feature_collection_string = feature_collection_string();
+        let mut feature_reader = FeatureReader::from_reader(feature_collection_string.as_bytes());
+        let members = feature_reader.collection_members().unwrap();
+        assert_eq!(members, &FeatureCollectionMembers::default());
+
+        let records: Vec<MyRecord> = feature_reader
+            .deserialize()
+            .expect("a valid feature collection")
+            .map(|result| result.expect("a valid feature"))
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].name, "Neverland");
+    }
+
+    #[test]
+    fn invalid_bbox_member() {
+        let feature_collection_string =
+            r#"{"type": "FeatureCollection", "bbox": "nope", "features": []}"#;
+        let mut feature_reader = FeatureReader::from_reader(feature_collection_string.as_bytes());
+        assert!(matches!(
+            feature_reader.collection_members(),
+            Err(crate::Error::BboxExpectedArray(_))
+        ));
+    }
+
+    fn with_error_locations(feature_collection_string: &str) -> FeatureReader<&[u8]> {
+        let options = ParseOptions {
+            error_locations: true,
+            ..ParseOptions::default()
+        };
+        FeatureReader::from_reader(feature_collection_string.as_bytes()).with_options(options)
+    }
+
+    #[test]
+    fn error_locations() {
+        let feature_collection_string = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "geometry": null, "properties": {}},
+            {"type": "Feature", "geometry": {"type": "Point", "coordinates": [1]}, "properties": {}}
+        ]}"#;
+        let mut features = with_error_locations(feature_collection_string).features();
+        assert!(features.next().unwrap().is_ok());
+        let err = features.next().unwrap().unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!(location.pointer, "/features/1/geometry/coordinates");
+        assert_eq!(location.feature_index, Some(1));
+        assert_eq!((location.line, location.column), (Some(3), Some(13)));
+        assert!(matches!(
+            err.without_location(),
+            crate::Error::PositionTooShort(1)
+        ));
+        assert!(features.next().is_none());
+
+        let feature_collection_string = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "geometry": null, "properties": {}},
+            {"type": "Feature", "geometry": null,
+             "properties": {"a": nope}}
+        ]}"#;
+        let mut features = with_error_locations(feature_collection_string).features();
+        assert!(features.next().unwrap().is_ok());
+        let err = features.next().unwrap().unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!(location.pointer, "/features/1");
+        assert_eq!(location.feature_index, Some(1));
+        // The same position serde_json reports for the whole document.
+        let json_err = serde_json::from_str::<JsonValue>(feature_collection_string).unwrap_err();
+        assert_eq!(
+            (location.line, location.column),
+            (Some(json_err.line()), Some(json_err.column()))
+        );
+        assert_eq!((location.line, location.column), (Some(4), Some(35)));
+        assert!(matches!(
+            err.without_location(),
+            crate::Error::MalformedJson(_)
+        ));
+
+        let mut feature_reader = with_error_locations(
+            r#"{"type": "FeatureCollection", "bbox": "nope", "features": []}"#,
+        );
+        let err = feature_reader.collection_members().unwrap_err();
+        assert_eq!(err.location().unwrap().pointer, "/bbox");
+        assert!(matches!(
+            err.without_location(),
+            crate::Error::BboxExpectedArray(_)
+        ));
+
+        // Without `error_locations`, errors aren't wrapped.
+        let mut features =
+            FeatureReader::from_reader(feature_collection_string.as_bytes()).features();
+        assert!(features.next().unwrap().is_ok());
+        assert!(matches!(
+            features.next(),
+            Some(Err(crate::Error::MalformedJson(_)))
+        ));
+    }
+
+    #[test]
+    fn skip_invalid_features() {
+        let feature_collection_string = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "geometry": null, "properties": {"n": 0}},
+            {"type": "Feature", "geometry": null, "properties": {"n": nope}},
+            3,
+            {"type": "Feature", "geometry": {"type": "Point", "coordinates": [1]}, "properties": {}},
+            {"type": "Feature", "geometry": null, "properties": {"n": "[\"}"}},
+            {"type": "Feature", "geometry": null, "properties": {"n": 1},},
+            null],
+            "name": "after"
+        }"#;
+        let mut features = with_error_locations(feature_collection_string)
+            .skip_invalid_features()
+            .features();
+        let results: Vec<_> = (&mut features)
+            .map(|result| result.map_err(|err| err.location().unwrap().feature_index.unwrap()))
+            .collect();
+        assert_eq!(results.len(), 7);
+        assert!(results[0].is_ok());
+        assert_eq!(results[1].as_ref().unwrap_err(), &1);
+        assert_eq!(results[2].as_ref().unwrap_err(), &2);
+        assert_eq!(results[3].as_ref().unwrap_err(), &3);
+        assert_eq!(results[4].as_ref().unwrap().property("n").unwrap(), "[\"}");
+        assert_eq!(results[5].as_ref().unwrap_err(), &5);
+        assert_eq!(results[6].as_ref().unwrap_err(), &6);
+        assert_eq!(
+            features
+                .collection_members()
+                .foreign_members
+                .as_ref()
+                .unwrap()["name"],
+            "after"
+        );
+
+        // Deserializing carries on too. Its features need a geometry, which only the fourth has.
+        let values: Vec<_> = FeatureReader::from_reader(feature_collection_string.as_bytes())
+            .skip_invalid_features()
+            .deserialize::<JsonValue>()
+            .unwrap()
+            .collect();
+        assert_eq!(values.len(), 7);
+        assert!(values[3].is_ok());
+    }
+
+    #[test]
+    fn skip_invalid_features_unbalanced() {
+        let feature_collection_string = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "geometry": null, "properties": {}},
+            {"type": "Feature", "geometry": null, "properties": {"a": "}]}
+        ]}"#;
+        let results: Vec<_> = FeatureReader::from_reader(feature_collection_string.as_bytes())
+            .skip_invalid_features()
+            .lazy_features()
+            .collect();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
 }
@@ -0,0 +1,250 @@
+use crate::de::feature_or_geometry_from_json_value;
+use crate::{util, Error, Feature, JsonValue, Result};
+
+use serde::de::DeserializeOwned;
+
+use std::io::{BufRead, BufReader, Read};
+
+/// The ASCII record separator which precedes each GeoJSON text in a sequence.
+pub(crate) const RECORD_SEPARATOR: u8 = 0x1E;
+
+/// Enumerates individual Features from a GeoJSON Text Sequence (`application/geo+json-seq`).
+///
+/// Each record of the sequence is a GeoJSON text preceded by an ASCII record separator (`0x1E`).
+/// Records holding a bare Geometry are read as a Feature with that geometry.
+///
+/// A record which can't be parsed, e.g. because it was truncated, produces an `Err`, but reading
+/// then resumes at the next record separator, as required by
+/// [RFC 7464 § 2.3](https://tools.ietf.org/html/rfc7464#section-2.3).
+///
+/// [GeoJSON Text Sequences (RFC 8142)](https://tools.ietf.org/html/rfc8142)
+pub struct FeatureSeqReader<R> {
+    reader: BufReader<R>,
+}
+
+impl<R: Read> FeatureSeqReader<R> {
+    /// Create a FeatureSeqReader from the given `reader`.
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+        }
+    }
+
+    /// Iterate over the individual [`Feature`s](Feature) of a GeoJSON Text Sequence.
+    ///
+    /// If instead you'd like to deserialize directly to your own struct, see
+    /// [`FeatureSeqReader::deserialize`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let sequence = "\x1e{\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[125.6,10.1]},\"properties\":{\"name\":\"Dinagat Islands\"}}\n\
+    ///                 \x1e{\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[2.3,4.5]},\"properties\":{\"name\":\"Neverland\"}}\n";
+    ///
+    /// use geojson::FeatureSeqReader;
+    /// let feature_reader = FeatureSeqReader::from_reader(sequence.as_bytes());
+    /// let names: Vec<String> = feature_reader
+    ///     .features()
+    ///     .map(|feature| {
+    ///         let feature = feature.expect("valid geojson feature");
+    ///         feature.property("name").unwrap().as_str().unwrap().to_string()
+    ///     })
+    ///     .collect();
+    /// assert_eq!(names, vec!["Dinagat Islands", "Neverland"]);
+    /// ```
+    pub fn features(self) -> impl Iterator<Item = Result<Feature>> {
+        self.records()
+            .map(|record| util::feature_from_feature_or_geometry(record?))
+    }
+
+    /// Deserialize the features of a GeoJSON Text Sequence into your own custom struct using the
+    /// [`serde`](../../serde) crate.
+    ///
+    /// See [`FeatureReader::deserialize`](crate::FeatureReader::deserialize) for the
+    /// requirements on your struct.
+    ///
+    /// # Examples
+    #[cfg_attr(feature = "geo-types", doc = "```")]
+    #[cfg_attr(not(feature = "geo-types"), doc = "```ignore")]
+    /// use geojson::{FeatureSeqReader, de::deserialize_geometry};
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct MyStruct {
+    ///     #[serde(deserialize_with = "deserialize_geometry")]
+    ///     geometry: geo_types::Point<f64>,
+    ///     name: String,
+    /// }
+    ///
+    /// let sequence = "\x1e{\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[125.6,10.1]},\"properties\":{\"name\":\"Dinagat Islands\"}}\n";
+    ///
+    /// let feature_reader = FeatureSeqReader::from_reader(sequence.as_bytes());
+    /// for my_struct in feature_reader.deserialize::<MyStruct>().unwrap() {
+    ///     let my_struct = my_struct.expect("valid geojson feature");
+    ///     assert_eq!(my_struct.name, "Dinagat Islands");
+    ///     assert_eq!(my_struct.geometry.x(), 125.6);
+    /// }
+    /// ```
+    pub fn deserialize<D: DeserializeOwned>(self) -> Result<impl Iterator<Item = Result<D>>> {
+        Ok(self
+            .records()
+            .map(|record| feature_or_geometry_from_json_value(record?)))
+    }
+
+    /// Parse each non-empty record of the sequence as JSON.
+    fn records(mut self) -> impl Iterator<Item = Result<JsonValue>> {
+        let mut buf = vec![];
+        let mut is_first_record = true;
+        std::iter::from_fn(move || loop {
+            buf.clear();
+            if let Err(err) = self.reader.read_until(RECORD_SEPARATOR, &mut buf) {
+                return Some(Err(err.into()));
+            }
+            let is_last_record = buf.last() != Some(&RECORD_SEPARATOR);
+            if is_last_record && buf.is_empty() {
+                return None;
+            }
+            if !is_last_record {
+                buf.pop();
+            }
+
+            // Multiple consecutive record separators don't denote empty records, and nothing is
+            // expected before the first record separator.
+            let record = trim_ascii_whitespace(&buf);
+            if record.is_empty() {
+                is_first_record = false;
+                continue;
+            }
+            if is_first_record {
+                is_first_record = false;
+                log::warn!("GeoJSON Text Sequence did not begin with a record separator");
+            }
+
+            return Some(parse_record(record));
+        })
+    }
+}
+
+fn trim_ascii_whitespace(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|byte| !byte.is_ascii_whitespace())
+        .map_or(start, |idx| idx + 1);
+    &bytes[start..end]
+}
+
+fn parse_record(record: &[u8]) -> Result<JsonValue> {
+    let value: JsonValue = serde_json::from_slice(record)?;
+    if let JsonValue::Object(_) = value {
+        Ok(value)
+    } else {
+        // This includes top-level numbers, which might have been truncated without
+        // serde_json being able to tell, see RFC 7464 § 2.4.
+        Err(Error::GeoJsonExpectedObject(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Geometry, Value};
+
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct MyRecord {
+        geometry: crate::Geometry,
+        name: String,
+        age: u64,
+    }
+
+    fn sequence() -> String {
+        [
+            r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[125.6,10.1]},"properties":{"name":"Dinagat Islands","age":123}}"#,
+            r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[2.3,4.5]},"properties":{"name":"Neverland","age":456}}"#,
+        ]
+        .iter()
+        .map(|record| format!("\x1e{}\n", record))
+        .collect()
+    }
+
+    #[test]
+    fn read_features() {
+        let sequence = sequence();
+        let features: Vec<Feature> = FeatureSeqReader::from_reader(sequence.as_bytes())
+            .features()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[0].property("age").unwrap(), 123);
+        assert_eq!(
+            features[1].geometry,
+            Some(Geometry::new(Value::Point(vec![2.3, 4.5])))
+        );
+    }
+
+    #[test]
+    fn read_geometry_record() {
+        let sequence = "\x1e{\"type\":\"Point\",\"coordinates\":[1.0,2.0]}\n";
+        let features: Vec<Feature> = FeatureSeqReader::from_reader(sequence.as_bytes())
+            .features()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(features, vec![Feature::from(Value::Point(vec![1.0, 2.0]))]);
+    }
+
+    #[test]
+    fn deserialize_into_type() {
+        let sequence = sequence();
+        let records: Vec<MyRecord> = FeatureSeqReader::from_reader(sequence.as_bytes())
+            .deserialize()
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name, "Dinagat Islands");
+        assert_eq!(records[1].age, 456);
+        assert_eq!(
+            records[1].geometry,
+            Geometry::new(Value::Point(vec![2.3, 4.5]))
+        );
+    }
+
+    #[test]
+    fn recover_from_truncated_records() {
+        let sequence = sequence();
+        let truncated = format!(
+            "\x1e\x1e{}\x1e{{\"type\":\"Feature\",\"geom\n\x1e12\x1e{}",
+            &sequence[1..40],
+            &sequence[1..]
+        );
+        let results: Vec<Result<Feature>> = FeatureSeqReader::from_reader(truncated.as_bytes())
+            .features()
+            .collect();
+        assert_eq!(results.len(), 5);
+        assert!(matches!(results[0], Err(Error::MalformedJson(_))));
+        assert!(matches!(results[1], Err(Error::MalformedJson(_))));
+        assert!(matches!(results[2], Err(Error::GeoJsonExpectedObject(_))));
+        assert_eq!(results[3].as_ref().unwrap().property("age").unwrap(), 123);
+        assert_eq!(results[4].as_ref().unwrap().property("age").unwrap(), 456);
+    }
+
+    #[test]
+    fn empty_sequence() {
+        assert_eq!(
+            FeatureSeqReader::from_reader("".as_bytes())
+                .features()
+                .count(),
+            0
+        );
+        assert_eq!(
+            FeatureSeqReader::from_reader("\x1e\n\x1e".as_bytes())
+                .features()
+                .count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::feature_seq_reader::RECORD_SEPARATOR;
+use crate::ser::to_feature_writer;
+use crate::{Feature, Result};
+
+use serde::Serialize;
+use std::io::Write;
+
+/// Write Features to a GeoJSON Text Sequence (`application/geo+json-seq`).
+///
+/// Every Feature is written as its own record: an ASCII record separator (`0x1E`), followed by the
+/// Feature, followed by a line feed. Because each record begins with a record separator, a
+/// truncated record left behind by an interrupted write will not corrupt the records written
+/// after it, see [RFC 7464 § 2.2](https://tools.ietf.org/html/rfc7464#section-2.2).
+///
+/// Unlike [`FeatureWriter`](crate::FeatureWriter), there's no enclosing FeatureCollection to close,
+/// so you can append more records to an existing sequence at any time.
+///
+/// [GeoJSON Text Sequences (RFC 8142)](https://tools.ietf.org/html/rfc8142)
+pub struct FeatureSeqWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> FeatureSeqWriter<W> {
+    /// Create a FeatureSeqWriter from the given `writer`.
+    ///
+    /// To append features from your custom structs, use [`FeatureSeqWriter::serialize`].
+    ///
+    /// To append features from [`Feature`] use [`FeatureSeqWriter::write_feature`].
+    pub fn from_writer(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Write a [`crate::Feature`] struct to the output stream. If you'd like to
+    /// serialize your own custom structs, see [`FeatureSeqWriter::serialize`] instead.
+    pub fn write_feature(&mut self, feature: &Feature) -> Result<()> {
+        self.writer.write_all(&[RECORD_SEPARATOR])?;
+        serde_json::to_writer(&mut self.writer, feature)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    /// Serialize your own custom struct as a Feature record using the [`serde`] crate.
+    ///
+    /// See [`FeatureWriter::serialize`](crate::FeatureWriter::serialize) for the requirements
+    /// on your struct.
+    ///
+    /// # Examples
+    #[cfg_attr(feature = "geo-types", doc = "```")]
+    #[cfg_attr(not(feature = "geo-types"), doc = "```ignore")]
+    /// use geojson::{FeatureSeqWriter, ser::serialize_geometry};
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct MyStruct {
+    ///     #[serde(serialize_with = "serialize_geometry")]
+    ///     geometry: geo_types::Point<f64>,
+    ///     name: String,
+    /// }
+    ///
+    /// let mut output: Vec<u8> = vec![];
+    /// {
+    ///     let mut feature_writer = FeatureSeqWriter::from_writer(&mut output);
+    ///     feature_writer
+    ///         .serialize(&MyStruct {
+    ///             geometry: geo_types::point!(x: 125.6, y: 10.1),
+    ///             name: "Dinagat Islands".to_string(),
+    ///         })
+    ///         .unwrap();
+    /// }
+    ///
+    /// assert_eq!(output[0], 0x1E);
+    /// assert_eq!(output.last(), Some(&b'\n'));
+    /// ```
+    pub fn serialize<S: Serialize>(&mut self, value: &S) -> Result<()> {
+        self.writer.write_all(&[RECORD_SEPARATOR])?;
+        to_feature_writer(&mut self.writer, value)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    /// Flush the underlying writer buffer.
+    pub fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FeatureSeqReader, Geometry, Value};
+
+    #[derive(Serialize)]
+    struct MyRecord {
+        geometry: Geometry,
+        name: String,
+        age: u64,
+    }
+
+    #[test]
+    fn write_feature() {
+        let mut buffer: Vec<u8> = vec![];
+        let feature = {
+            let mut feature = Feature::from(Value::Point(vec![1.1, 1.2]));
+            feature.set_property("name", "Mishka");
+            feature
+        };
+        {
+            let mut writer = FeatureSeqWriter::from_writer(&mut buffer);
+            writer.write_feature(&feature).unwrap();
+            writer.write_feature(&feature).unwrap();
+        }
+
+        let record = format!("\x1e{}\n", serde_json::to_string(&feature).unwrap());
+        assert_eq!(String::from_utf8(buffer).unwrap(), record.repeat(2));
+    }
+
+    #[test]
+    fn serialize() {
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let mut writer = FeatureSeqWriter::from_writer(&mut buffer);
+            writer
+                .serialize(&MyRecord {
+                    geometry: Geometry::from(Value::Point(vec![1.1, 1.2])),
+                    name: "Mishka".to_string(),
+                    age: 12,
+                })
+                .unwrap();
+            writer
+                .serialize(&MyRecord {
+                    geometry: Geometry::from(Value::Point(vec![2.1, 2.2])),
+                    name: "Jane".to_string(),
+                    age: 22,
+                })
+                .unwrap();
+        }
+
+        let features: Vec<Feature> = FeatureSeqReader::from_reader(buffer.as_slice())
+            .features()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[0].property("name").unwrap(), "Mishka");
+        assert_eq!(features[1].property("age").unwrap(), 22);
+    }
+
+    #[test]
+    fn append_after_truncated_record() {
+        let mut buffer: Vec<u8> = b"\x1e{\"type\":\"Feature\",\"geo".to_vec();
+        {
+            let mut writer = FeatureSeqWriter::from_writer(&mut buffer);
+            writer
+                .write_feature(&Feature::from(Value::Point(vec![1.1, 1.2])))
+                .unwrap();
+        }
+
+        let mut results = FeatureSeqReader::from_reader(buffer.as_slice()).features();
+        assert!(results.next().unwrap().is_err());
+        assert!(results.next().unwrap().is_ok());
+        assert!(results.next().is_none());
+    }
+}
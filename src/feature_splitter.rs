@@ -0,0 +1,345 @@
+use crate::feature_iterator::{invalid_input, read_member, unexpected_byte};
+use crate::{Error, FeatureCollectionMembers, JsonValue, Result};
+
+use std::io;
+
+/// Splits a GeoJSON FeatureCollection, which is handed over in arbitrary chunks of bytes, into
+/// the raw bytes of each element of its `features` array.
+///
+/// This is the push-based counterpart of the tokenizer in
+/// [`FeatureIterator`](crate::FeatureIterator), for sources which can't be read from
+/// synchronously. Like it, the top-level members other than `features` may appear in any order
+/// and are collected into [`FeatureCollectionMembers`].
+pub(crate) struct FeatureSplitter {
+    state: State,
+    members: FeatureCollectionMembers,
+    seen_features: bool,
+    key: String,
+    buf: Vec<u8>,
+    scanner: Scanner,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum State {
+    /// Expecting the opening `{` of the FeatureCollection.
+    Start,
+    /// Expecting the first member name, or `}`.
+    FirstKey,
+    /// Expecting a member name, after a `,`.
+    NextKey,
+    /// Copying a member name.
+    Key,
+    /// Expecting the `:` after a member name.
+    Colon,
+    /// Expecting the start of a member value.
+    ValueStart,
+    /// Copying a member value.
+    Value,
+    /// Expecting `,` or `}` after a member value.
+    AfterValue,
+    /// Expecting the first element of the `features` array, or `]`.
+    FirstFeature,
+    /// Expecting an element of the `features` array, after a `,`.
+    NextFeature,
+    /// Copying an element of the `features` array.
+    Feature,
+    /// Expecting `,` or `]` after an element of the `features` array.
+    AfterFeature,
+    /// The FeatureCollection is complete, only whitespace may follow.
+    End,
+    /// A previous error left the input in an unknown state.
+    Failed,
+}
+
+impl FeatureSplitter {
+    pub(crate) fn new() -> Self {
+        FeatureSplitter {
+            state: State::Start,
+            members: FeatureCollectionMembers::default(),
+            seen_features: false,
+            key: String::new(),
+            buf: vec![],
+            scanner: Scanner::default(),
+        }
+    }
+
+    /// The members of the FeatureCollection, other than `type` and `features`, which have been
+    /// read so far.
+    pub(crate) fn members(&self) -> &FeatureCollectionMembers {
+        &self.members
+    }
+
+    /// Whether an error has been encountered, after which all further input is rejected.
+    pub(crate) fn is_failed(&self) -> bool {
+        self.state == State::Failed
+    }
+
+    /// Reject all further input, e.g. because a feature couldn't be parsed.
+    pub(crate) fn fail(&mut self) {
+        self.state = State::Failed;
+    }
+
+    /// Consume the next chunk of input, appending the raw bytes of every feature completed by it
+    /// to `features`.
+    ///
+    /// After an error, the input can't be resynchronized, so every further call fails.
+    pub(crate) fn feed(&mut self, bytes: &[u8], features: &mut Vec<Vec<u8>>) -> Result<()> {
+        if self.state == State::Failed {
+            return Err(invalid_input("FeatureCollection input previously failed"));
+        }
+        let mut i = 0;
+        while i < bytes.len() {
+            match self.push_byte(bytes[i], features) {
+                Ok(true) => i += 1,
+                // The byte terminated a number or literal, and still needs to be handled.
+                Ok(false) => {}
+                Err(err) => {
+                    self.state = State::Failed;
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Signal the end of input, failing if the FeatureCollection is incomplete.
+    pub(crate) fn finish(&mut self) -> Result<()> {
+        match self.state {
+            State::End => Ok(()),
+            State::Failed => Err(invalid_input("FeatureCollection input previously failed")),
+            _ => {
+                self.state = State::Failed;
+                Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "unexpected end of FeatureCollection",
+                )
+                .into())
+            }
+        }
+    }
+
+    /// Handle a single byte, returning whether it was consumed.
+    fn push_byte(&mut self, byte: u8, features: &mut Vec<Vec<u8>>) -> Result<bool> {
+        let is_whitespace = byte.is_ascii_whitespace();
+        match self.state {
+            State::Key | State::Value | State::Feature => {
+                let step = self.scanner.push(byte);
+                if step != Step::Delimiter {
+                    self.buf.push(byte);
+                }
+                if step == Step::Continue {
+                    return Ok(true);
+                }
+                let bytes = std::mem::take(&mut self.buf);
+                match self.state {
+                    State::Key => {
+                        self.key = serde_json::from_slice(&bytes)?;
+                        self.state = State::Colon;
+                    }
+                    State::Value => {
+                        let value: JsonValue = serde_json::from_slice(&bytes)?;
+                        read_member(&mut self.members, std::mem::take(&mut self.key), value)?;
+                        self.state = State::AfterValue;
+                    }
+                    _ => {
+                        features.push(bytes);
+                        self.state = State::AfterFeature;
+                    }
+                }
+                return Ok(step == Step::Complete);
+            }
+            _ if is_whitespace => return Ok(true),
+            State::Start => match byte {
+                b'{' => self.state = State::FirstKey,
+                other => return Err(unexpected_byte("`{`", other)),
+            },
+            State::FirstKey | State::NextKey => match byte {
+                b'"' => self.start_value(byte, State::Key),
+                b'}' if self.state == State::FirstKey => self.end_collection()?,
+                other => return Err(unexpected_byte("a member name", other)),
+            },
+            State::Colon => match byte {
+                b':' => self.state = State::ValueStart,
+                other => return Err(unexpected_byte("`:`", other)),
+            },
+            State::ValueStart => {
+                if self.key == "features" && byte == b'[' {
+                    if self.seen_features {
+                        return Err(invalid_input("duplicate `features` member"));
+                    }
+                    self.seen_features = true;
+                    self.state = State::FirstFeature;
+                } else {
+                    self.start_value(byte, State::Value);
+                }
+            }
+            State::AfterValue => match byte {
+                b',' => self.state = State::NextKey,
+                b'}' => self.end_collection()?,
+                other => return Err(unexpected_byte("`,` or `}`", other)),
+            },
+            State::FirstFeature | State::NextFeature => match byte {
+                b']' if self.state == State::FirstFeature => self.state = State::AfterValue,
+                _ => self.start_value(byte, State::Feature),
+            },
+            State::AfterFeature => match byte {
+                b',' => self.state = State::NextFeature,
+                b']' => self.state = State::AfterValue,
+                other => return Err(unexpected_byte("`,` or `]`", other)),
+            },
+            State::End => return Err(unexpected_byte("end of input", byte)),
+            State::Failed => unreachable!("feed returns early once failed"),
+        }
+        Ok(true)
+    }
+
+    fn start_value(&mut self, first_byte: u8, state: State) {
+        self.buf.clear();
+        self.buf.push(first_byte);
+        self.scanner = Scanner::new(first_byte);
+        self.state = state;
+    }
+
+    fn end_collection(&mut self) -> Result<()> {
+        if !self.seen_features {
+            return Err(Error::ExpectedProperty("features".to_string()));
+        }
+        self.state = State::End;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Step {
+    /// The value continues after this byte.
+    Continue,
+    /// This byte was the last one of the value.
+    Complete,
+    /// This byte follows a number or literal, and isn't part of it.
+    Delimiter,
+}
+
+/// Tracks where the end of a single JSON value is, one byte at a time.
+#[derive(Debug, Default)]
+struct Scanner {
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    scalar: bool,
+}
+
+impl Scanner {
+    /// Start scanning a value beginning with `first_byte`, which has already been consumed.
+    fn new(first_byte: u8) -> Self {
+        let mut scanner = Scanner::default();
+        match first_byte {
+            b'"' => scanner.in_string = true,
+            b'{' | b'[' => scanner.depth = 1,
+            _ => scanner.scalar = true,
+        }
+        scanner
+    }
+
+    fn push(&mut self, byte: u8) -> Step {
+        if self.scalar {
+            return if byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'-' | b'.') {
+                Step::Continue
+            } else {
+                Step::Delimiter
+            };
+        }
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+            } else if byte == b'\\' {
+                self.escaped = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+                if self.depth == 0 {
+                    return Step::Complete;
+                }
+            }
+            return Step::Continue;
+        }
+        match byte {
+            b'"' => self.in_string = true,
+            b'{' | b'[' => self.depth += 1,
+            b'}' | b']' => {
+                self.depth -= 1;
+                if self.depth == 0 {
+                    return Step::Complete;
+                }
+            }
+            _ => {}
+        }
+        Step::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(chunks: &[&str]) -> Result<(Vec<String>, FeatureCollectionMembers)> {
+        let mut splitter = FeatureSplitter::new();
+        let mut features = vec![];
+        for chunk in chunks {
+            splitter.feed(chunk.as_bytes(), &mut features)?;
+        }
+        splitter.finish()?;
+        let features = features
+            .into_iter()
+            .map(|bytes| String::from_utf8(bytes).unwrap())
+            .collect();
+        Ok((features, splitter.members().clone()))
+    }
+
+    #[test]
+    fn split_features() {
+        let input = r#"{"bbox": [1, 2, 3, 4], "type": "FeatureCollection", "features": [{"type": "Feature", "properties": {"a]": "}\""}}, 12, "x" ], "name": "fc" }"#;
+        let (features, members) = split(&[input]).unwrap();
+        assert_eq!(
+            features,
+            vec![
+                r#"{"type": "Feature", "properties": {"a]": "}\""}}"#,
+                "12",
+                r#""x""#
+            ]
+        );
+        assert_eq!(members.bbox, Some(vec![1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(members.foreign_members.unwrap().get("name").unwrap(), "fc");
+    }
+
+    #[test]
+    fn split_across_chunks() {
+        let input =
+            r#"{"type":"FeatureCollection","n":-1.5e3,"features":[{"id":"a\"b"},{"id":2}]}"#;
+        let (expected, _) = split(&[input]).unwrap();
+        for size in 1..input.len() {
+            let chunks: Vec<&str> = input
+                .as_bytes()
+                .chunks(size)
+                .map(|chunk| std::str::from_utf8(chunk).unwrap())
+                .collect();
+            let (features, members) = split(&chunks).unwrap();
+            assert_eq!(features, expected);
+            assert_eq!(members.foreign_members.unwrap().get("n").unwrap(), -1500.0);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            split(&[r#"{"type": "FeatureCollection"}"#]),
+            Err(Error::ExpectedProperty(_))
+        ));
+        assert!(matches!(
+            split(&[r#"{"type": "Feature", "features": []}"#]),
+            Err(Error::ExpectedType { .. })
+        ));
+        assert!(split(&[r#"{"features": [], "features": []}"#]).is_err());
+        assert!(split(&[r#"{"features": [{}"#]).is_err());
+        assert!(split(&[r#"{"features": []} x"#]).is_err());
+        assert!(split(&[r#"{"features": []}"#, "\n"]).is_ok());
+    }
+}
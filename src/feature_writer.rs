@@ -1,8 +1,12 @@
-use crate::ser::to_feature_writer;
-use crate::{Error, Feature, Result};
+use crate::object_visitor::GeoJsonObject;
+use crate::ser::{FeatureMembers, FeatureWrapper};
+use crate::util::Extent;
+use crate::write_options::round;
+use crate::{Bbox, Error, Feature, Geometry, JsonObject, JsonValue, LazyFeature, Result};
+use crate::{WithOptions, WriteOptions};
 
-use serde::Serialize;
-use std::io::Write;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Seek, SeekFrom, Write};
 
 #[derive(PartialEq)]
 enum State {
@@ -11,10 +15,88 @@ enum State {
     Finished,
 }
 
+/// The members of the FeatureCollection which the writer writes itself, rather than taking them
+/// from the foreign or trailing members.
+const RESERVED_MEMBERS: [&str; 3] = ["type", "features", "bbox"];
+
+/// Where a [`FeatureWriter`] writes the `type` member of the FeatureCollection.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TypePosition {
+    /// Before any other member, so that streaming readers know what they're reading straight
+    /// away. This is the default.
+    #[default]
+    First,
+    /// After the members given to [`FeatureWriter::with_foreign_members`] and
+    /// [`FeatureWriter::with_bbox`], just before `features`.
+    BeforeFeatures,
+    /// After `features` and any trailing members, as the last member.
+    Last,
+}
+
+fn without_reserved_members(members: JsonObject) -> impl Iterator<Item = (String, JsonValue)> {
+    members
+        .into_iter()
+        .filter(|(name, _)| !RESERVED_MEMBERS.contains(&name.as_str()))
+}
+
 /// Write Features to a FeatureCollection
+///
+/// By default the output is compact, with only the `type` and `features` members. Builder methods
+/// can indent the output, add members to the FeatureCollection, and move its `type` member.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Feature, FeatureWriter, TypePosition, Value};
+/// use serde_json::json;
+///
+/// let mut output: Vec<u8> = vec![];
+/// {
+///     let mut writer = FeatureWriter::from_writer(&mut output)
+///         .pretty()
+///         .with_type_position(TypePosition::BeforeFeatures)
+///         .with_foreign_members(json!({ "name": "points" }).as_object().unwrap().clone());
+///     writer
+///         .write_feature(&Feature::from(Value::Point(vec![1.0, 2.0])))
+///         .unwrap();
+///     writer.insert_trailing_member("count", json!(1)).unwrap();
+/// }
+///
+/// let expected = r#"{
+///   "name": "points",
+///   "type": "FeatureCollection",
+///   "features": [
+///     {
+///       "geometry": {
+///         "coordinates": [
+///           1.0,
+///           2.0
+///         ],
+///         "type": "Point"
+///       },
+///       "properties": {},
+///       "type": "Feature"
+///     }
+///   ],
+///   "count": 1
+/// }"#;
+/// assert_eq!(String::from_utf8(output).unwrap(), expected);
+/// ```
 pub struct FeatureWriter<W: Write> {
     writer: W,
     state: State,
+    options: WriteOptions,
+    pretty: bool,
+    type_position: TypePosition,
+    header_members: JsonObject,
+    trailing_members: JsonObject,
+    /// The extent of the written geometries, if the bbox is computed.
+    extent: Option<Extent>,
+    /// Seeks within a writer which can, to write the computed bbox in the header.
+    seek: Option<fn(&mut W, SeekFrom) -> io::Result<u64>>,
+    /// Where the space for the computed bbox in the header starts, and whether it's the first
+    /// member.
+    header_bbox: Option<(u64, bool)>,
 }
 
 impl<W: Write> FeatureWriter<W> {
@@ -27,28 +109,161 @@ impl<W: Write> FeatureWriter<W> {
         Self {
             writer,
             state: State::New,
+            options: WriteOptions::default(),
+            pretty: false,
+            type_position: TypePosition::default(),
+            header_members: JsonObject::new(),
+            trailing_members: JsonObject::new(),
+            extent: None,
+            seek: None,
+            header_bbox: None,
+        }
+    }
+
+    /// Indent the output, with each member and array element on a line of its own, for people to
+    /// read.
+    pub fn pretty(mut self) -> Self {
+        self.pretty = true;
+        self
+    }
+
+    /// Write the `type` member of the FeatureCollection at `position`.
+    pub fn with_type_position(mut self, position: TypePosition) -> Self {
+        self.type_position = position;
+        self
+    }
+
+    /// Write `bbox` as the bbox of the FeatureCollection, before its `features`.
+    pub fn with_bbox(mut self, bbox: Bbox) -> Self {
+        self.header_members
+            .insert("bbox".to_string(), JsonValue::from(bbox));
+        self
+    }
+
+    /// Compute the bbox of the FeatureCollection from the positions of the geometries of the
+    /// features, and write it after `features` when the writer finishes.
+    ///
+    /// This replaces any bbox given to [`FeatureWriter::with_bbox`]. If no feature has a
+    /// position, no bbox is written. See [`FeatureWriter::with_computed_bbox_in_header`] to
+    /// write it before `features`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureWriter, Value};
+    ///
+    /// let mut output: Vec<u8> = vec![];
+    /// {
+    ///     let mut writer = FeatureWriter::from_writer(&mut output).with_computed_bbox();
+    ///     writer
+    ///         .write_feature(&Feature::from(Value::Point(vec![1.0, 4.0])))
+    ///         .unwrap();
+    ///     writer
+    ///         .write_feature(&Feature::from(Value::Point(vec![3.0, 2.0])))
+    ///         .unwrap();
+    /// }
+    /// let output = String::from_utf8(output).unwrap();
+    /// assert!(output.ends_with(r#"], "bbox": [1.0,2.0,3.0,4.0]}"#));
+    /// ```
+    pub fn with_computed_bbox(mut self) -> Self {
+        self.extent = Some(Extent::default());
+        self
+    }
+
+    /// Write `members`, like a `name`, as foreign members of the FeatureCollection, before its
+    /// `features`.
+    ///
+    /// Members named `type`, `features` or `bbox` are ignored, since the writer writes those
+    /// itself. Use [`FeatureWriter::with_bbox`] to give the bbox.
+    pub fn with_foreign_members(mut self, members: JsonObject) -> Self {
+        self.header_members
+            .extend(without_reserved_members(members));
+        self
+    }
+
+    /// Write `members` after the `features` of the FeatureCollection, when the writer finishes.
+    ///
+    /// As with [`FeatureWriter::with_foreign_members`], members named `type`, `features` or
+    /// `bbox` are ignored.
+    pub fn with_trailing_members(mut self, members: JsonObject) -> Self {
+        self.trailing_members
+            .extend(without_reserved_members(members));
+        self
+    }
+
+    /// Add a member to write after the `features` of the FeatureCollection, e.g. a value computed
+    /// from the features which have been written.
+    ///
+    /// The writer writes the `type`, `features` and `bbox` members itself, so adding a member
+    /// with one of those names is an error.
+    pub fn insert_trailing_member(
+        &mut self,
+        name: impl Into<String>,
+        value: JsonValue,
+    ) -> Result<()> {
+        if self.state == State::Finished {
+            return Err(Error::InvalidWriterState(
+                "cannot add a member when writer has already finished",
+            ));
+        }
+        let name = name.into();
+        if RESERVED_MEMBERS.contains(&name.as_str()) {
+            return Err(Error::InvalidWriterState(
+                "cannot add a member named `type`, `features` or `bbox`",
+            ));
         }
+        self.trailing_members.insert(name, value);
+        Ok(())
+    }
+
+    /// Write the features with `options`, e.g. to round their coordinates.
+    ///
+    /// Features written by [`FeatureWriter::write_lazy_feature`] are copied unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureWriter, Value, WriteOptions};
+    ///
+    /// let mut output: Vec<u8> = vec![];
+    /// {
+    ///     let options = WriteOptions {
+    ///         coordinate_precision: Some(2),
+    ///         ..WriteOptions::default()
+    ///     };
+    ///     let mut writer = FeatureWriter::from_writer(&mut output).with_options(options);
+    ///     writer
+    ///         .write_feature(&Feature::from(Value::Point(vec![1.23456, 2.5])))
+    ///         .unwrap();
+    /// }
+    /// let output = String::from_utf8(output).unwrap();
+    /// assert!(output.contains(r#""coordinates":[1.23,2.5]"#));
+    /// ```
+    pub fn with_options(mut self, options: WriteOptions) -> Self {
+        self.options = options;
+        self
     }
 
     /// Write a [`crate::Feature`] struct to the output stream. If you'd like to
     /// serialize your own custom structs, see [`FeatureWriter::serialize`] instead.
     pub fn write_feature(&mut self, feature: &Feature) -> Result<()> {
-        match self.state {
-            State::Finished => {
-                return Err(Error::InvalidWriterState(
-                    "cannot write another Feature when writer has already finished",
-                ))
-            }
-            State::New => {
-                self.write_prefix()?;
-                self.state = State::Started;
-            }
-            State::Started => {
-                self.write_str(",")?;
-            }
-        }
-        serde_json::to_writer(&mut self.writer, feature)?;
-        Ok(())
+        self.start_feature("cannot write another Feature when writer has already finished")?;
+        self.add_to_extent(feature.geometry.as_ref());
+        let options = self.options.clone();
+        self.write_json(&WithOptions::new(feature, &options), FEATURE_INDENT)
+    }
+
+    /// Write a [`LazyFeature`] to the output stream, copying its original JSON text unchanged.
+    pub fn write_lazy_feature(&mut self, feature: &LazyFeature) -> Result<()> {
+        // Parse the geometry before writing anything, so that an invalid one leaves the output
+        // as it was.
+        let geometry = match self.extent {
+            Some(_) => feature.geometry()?,
+            None => None,
+        };
+        self.start_feature("cannot write another Feature when writer has already finished")?;
+        self.add_to_extent(geometry.as_ref());
+        self.write_str(feature.as_raw().get())
     }
 
     /// Serialize your own custom struct to the features of a FeatureCollection using the
@@ -150,21 +365,22 @@ impl<W: Write> FeatureWriter<W> {
     /// }
     /// ```
     pub fn serialize<S: Serialize>(&mut self, value: &S) -> Result<()> {
-        match self.state {
-            State::Finished => {
-                return Err(Error::InvalidWriterState(
-                    "cannot serialize another record when writer has already finished",
-                ))
-            }
-            State::New => {
-                self.write_prefix()?;
-                self.state = State::Started;
-            }
-            State::Started => {
-                self.write_str(",")?;
-            }
+        let finished_message = "cannot serialize another record when writer has already finished";
+        let options = self.options.clone();
+        if self.extent.is_none() {
+            self.start_feature(finished_message)?;
+            return self.write_json(&FeatureWrapper::new(value, &options), FEATURE_INDENT);
         }
-        to_feature_writer(&mut self.writer, value)
+        // Convert the record before writing anything, so that an invalid geometry leaves the
+        // output as it was. The geometry is read from the record's JSON by reference.
+        let members = FeatureMembers::new(serde_json::to_value(value)?, &options)?;
+        let geometry = match &members.geometry {
+            Some(JsonValue::Null) | None => None,
+            Some(geometry) => Some(GeoJsonObject::deserialize(geometry)?.into_geometry()?),
+        };
+        self.start_feature(finished_message)?;
+        self.add_to_extent(geometry.as_ref());
+        self.write_json(&members, FEATURE_INDENT)
     }
 
     /// Writes the closing syntax for the FeatureCollection.
@@ -181,11 +397,11 @@ impl<W: Write> FeatureWriter<W> {
             State::New => {
                 self.state = State::Finished;
                 self.write_prefix()?;
-                self.write_suffix()?;
+                self.write_suffix(false)?;
             }
             State::Started => {
                 self.state = State::Finished;
-                self.write_suffix()?;
+                self.write_suffix(true)?;
             }
         }
         Ok(())
@@ -199,12 +415,149 @@ impl<W: Write> FeatureWriter<W> {
         Ok(self.writer.flush()?)
     }
 
+    /// Write the prefix before the first feature, or a separator before the others.
+    fn start_feature(&mut self, finished_message: &'static str) -> Result<()> {
+        match self.state {
+            State::Finished => return Err(Error::InvalidWriterState(finished_message)),
+            State::New => {
+                self.write_prefix()?;
+                self.state = State::Started;
+            }
+            State::Started => {
+                self.write_str(",")?;
+            }
+        }
+        if self.pretty {
+            self.write_str(FEATURE_INDENT)?;
+        }
+        Ok(())
+    }
+
     fn write_prefix(&mut self) -> Result<()> {
-        self.write_str(r#"{ "type": "FeatureCollection", "features": ["#)
+        self.write_str("{")?;
+        let mut first = true;
+        if self.type_position == TypePosition::First {
+            self.write_member("type", &JsonValue::from("FeatureCollection"), first)?;
+            first = false;
+        }
+        if self.extent.is_some() {
+            self.header_members.remove("bbox");
+        }
+        for (name, value) in std::mem::take(&mut self.header_members) {
+            self.write_member(&name, &value, first)?;
+            first = false;
+        }
+        if self.type_position == TypePosition::BeforeFeatures {
+            self.write_member("type", &JsonValue::from("FeatureCollection"), first)?;
+            first = false;
+        }
+        if let Some(seek) = self.seek {
+            // Leave room for the bbox, to be filled in when the writer finishes. If it's the first
+            // member, its separator follows it, so that the room can be left blank.
+            let position = seek(&mut self.writer, SeekFrom::Current(0))?;
+            self.write_str(&" ".repeat(HEADER_BBOX_LEN))?;
+            self.header_bbox = Some((position, first));
+        }
+        self.write_member_name("features", first)?;
+        self.write_str("[")
+    }
+
+    fn write_suffix(&mut self, has_features: bool) -> Result<()> {
+        if self.pretty && has_features {
+            self.write_str(MEMBER_INDENT)?;
+        }
+        self.write_str("]")?;
+
+        let mut header_bbox = None;
+        if let Some(bbox) = self.computed_bbox() {
+            self.trailing_members.remove("bbox");
+            let bbox = JsonValue::from(bbox);
+            match self.header_bbox {
+                Some((_, first)) => header_bbox = Some(self.header_bbox_text(&bbox, first)),
+                None => self.write_member("bbox", &bbox, false)?,
+            }
+        }
+        for (name, value) in std::mem::take(&mut self.trailing_members) {
+            self.write_member(&name, &value, false)?;
+        }
+        if self.type_position == TypePosition::Last {
+            self.write_member("type", &JsonValue::from("FeatureCollection"), false)?;
+        }
+        if self.pretty {
+            self.write_str("\n")?;
+        }
+        self.write_str("}")?;
+
+        if let (Some(seek), Some((position, _)), Some(text)) =
+            (self.seek, self.header_bbox, header_bbox)
+        {
+            let end = seek(&mut self.writer, SeekFrom::Current(0))?;
+            seek(&mut self.writer, SeekFrom::Start(position))?;
+            self.write_str(&text)?;
+            seek(&mut self.writer, SeekFrom::Start(end))?;
+        }
+        Ok(())
+    }
+
+    fn add_to_extent(&mut self, geometry: Option<&Geometry>) {
+        if let (Some(extent), Some(geometry)) = (&mut self.extent, geometry) {
+            extent.add_value(&geometry.value);
+        }
+    }
+
+    /// The computed bbox, rounded if the options ask for it.
+    fn computed_bbox(&self) -> Option<Bbox> {
+        let mut bbox = self.extent.as_ref()?.to_bbox()?;
+        if let Some(scale) = self.options.bbox_scale() {
+            bbox.iter_mut()
+                .for_each(|value| *value = round(*value, scale));
+        }
+        Some(bbox)
+    }
+
+    /// The bbox member to fill the room left in the header with, on one line.
+    fn header_bbox_text(&self, bbox: &JsonValue, first: bool) -> String {
+        let indent = if self.pretty { MEMBER_INDENT } else { " " };
+        let member = format!(r#"{}"bbox": {}"#, indent, bbox);
+        if first {
+            format!("{},", member)
+        } else {
+            format!(",{}", member)
+        }
     }
 
-    fn write_suffix(&mut self) -> Result<()> {
-        self.write_str("]}")
+    fn write_member(&mut self, name: &str, value: &JsonValue, first: bool) -> Result<()> {
+        self.write_member_name(name, first)?;
+        self.write_json(value, MEMBER_INDENT)
+    }
+
+    /// Write the name of a member of the FeatureCollection, preceded by a separator unless it's
+    /// the `first` member.
+    fn write_member_name(&mut self, name: &str, first: bool) -> Result<()> {
+        let separator = match (self.pretty, first) {
+            (true, true) => "",
+            (true, false) => ",",
+            (false, true) => " ",
+            (false, false) => ", ",
+        };
+        self.write_str(separator)?;
+        if self.pretty {
+            self.write_str(MEMBER_INDENT)?;
+        }
+        serde_json::to_writer(&mut self.writer, name)?;
+        self.write_str(": ")
+    }
+
+    /// Write `value` as JSON, or if the output is pretty, indented as if each line started with
+    /// `indent`.
+    fn write_json<T: Serialize + ?Sized>(&mut self, value: &T, indent: &str) -> Result<()> {
+        if self.pretty {
+            let json = serde_json::to_string_pretty(value)?;
+            self.write_str(&json.replace('\n', indent))
+        } else {
+            serde_json::to_writer(&mut self.writer, value)?;
+            Ok(())
+        }
     }
 
     fn write_str(&mut self, text: &str) -> Result<()> {
@@ -213,6 +566,43 @@ impl<W: Write> FeatureWriter<W> {
     }
 }
 
+/// The start of a line holding a member of the FeatureCollection in pretty output.
+const MEMBER_INDENT: &str = "\n  ";
+/// The start of a line holding a feature in pretty output.
+const FEATURE_INDENT: &str = "\n    ";
+/// The room left in the header for a computed bbox, which is enough for any, since it has at
+/// most 3 dimensions.
+const HEADER_BBOX_LEN: usize = 176;
+
+impl<W: Write + Seek> FeatureWriter<W> {
+    /// Compute the bbox of the FeatureCollection like [`FeatureWriter::with_computed_bbox`], but
+    /// write it before `features`, by leaving room for it and seeking back to fill it in when the
+    /// writer finishes.
+    ///
+    /// The room is padded with spaces, and left blank if no feature has a position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureWriter, Value};
+    /// use std::io::Cursor;
+    ///
+    /// let mut output = Cursor::new(vec![]);
+    /// {
+    ///     let mut writer = FeatureWriter::from_writer(&mut output).with_computed_bbox_in_header();
+    ///     writer
+    ///         .write_feature(&Feature::from(Value::Point(vec![1.0, 2.0])))
+    ///         .unwrap();
+    /// }
+    /// let output = String::from_utf8(output.into_inner()).unwrap();
+    /// assert!(output.starts_with(r#"{ "type": "FeatureCollection", "bbox": [1.0,2.0,1.0,2.0] "#));
+    /// ```
+    pub fn with_computed_bbox_in_header(mut self) -> Self {
+        self.seek = Some(W::seek);
+        self.with_computed_bbox()
+    }
+}
+
 impl<W: Write> Drop for FeatureWriter<W> {
     fn drop(&mut self) {
         if self.state != State::Finished {
@@ -334,6 +724,32 @@ mod tests {
         assert_eq!(actual_json, expected)
     }
 
+    #[test]
+    fn write_lazy_feature() {
+        let features = [
+            r#"{"type":"Feature", "properties": {"n": 1.50, "s": "é"}, "geometry": null}"#,
+            r#"{ "geometry": {"coordinates": [1e2, 2], "type": "Point"}, "type": "Feature" }"#,
+        ];
+        let input = format!(
+            r#"{{"type": "FeatureCollection", "features": [{}]}}"#,
+            features.join(",")
+        );
+
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let mut writer = FeatureWriter::from_writer(&mut buffer);
+            for feature in crate::FeatureReader::from_reader(input.as_bytes()).lazy_features() {
+                writer.write_lazy_feature(&feature.unwrap()).unwrap();
+            }
+        }
+
+        let expected = format!(
+            r#"{{ "type": "FeatureCollection", "features": [{}]}}"#,
+            features.join(",")
+        );
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+    }
+
     #[test]
     fn serialize() {
         let mut buffer: Vec<u8> = vec![];
@@ -378,6 +794,320 @@ mod tests {
         assert_eq!(actual_json, expected)
     }
 
+    #[test]
+    fn members() {
+        let members = |value: JsonValue| value.as_object().unwrap().clone();
+        let write = |type_position| {
+            let mut buffer: Vec<u8> = vec![];
+            {
+                let mut writer = FeatureWriter::from_writer(&mut buffer)
+                    .with_type_position(type_position)
+                    .with_bbox(vec![1.0, 2.0, 3.0, 4.0])
+                    .with_foreign_members(members(json!({ "name": "a \"b\"" })))
+                    .with_trailing_members(members(json!({ "count": 0 })));
+                writer.insert_trailing_member("count", json!(1)).unwrap();
+                writer.insert_trailing_member("done", json!(true)).unwrap();
+                writer
+                    .write_feature(&Feature::from(crate::Value::Point(vec![1.5, 2.0])))
+                    .unwrap();
+                writer.finish().unwrap();
+                assert!(writer.insert_trailing_member("late", json!(1)).is_err());
+            }
+            String::from_utf8(buffer).unwrap()
+        };
+
+        let feature = r#"{"geometry":{"coordinates":[1.5,2.0],"type":"Point"},"properties":{},"type":"Feature"}"#;
+        assert_eq!(
+            write(TypePosition::First),
+            format!(
+                r#"{{ "type": "FeatureCollection", "bbox": [1.0,2.0,3.0,4.0], "name": "a \"b\"", "features": [{}], "count": 1, "done": true}}"#,
+                feature
+            )
+        );
+        assert_eq!(
+            write(TypePosition::BeforeFeatures),
+            format!(
+                r#"{{ "bbox": [1.0,2.0,3.0,4.0], "name": "a \"b\"", "type": "FeatureCollection", "features": [{}], "count": 1, "done": true}}"#,
+                feature
+            )
+        );
+        assert_eq!(
+            write(TypePosition::Last),
+            format!(
+                r#"{{ "bbox": [1.0,2.0,3.0,4.0], "name": "a \"b\"", "features": [{}], "count": 1, "done": true, "type": "FeatureCollection"}}"#,
+                feature
+            )
+        );
+    }
+
+    #[test]
+    fn reserved_members() {
+        let members = |value: JsonValue| value.as_object().unwrap().clone();
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let reserved = json!({ "type": "Feature", "features": 1, "bbox": [0] });
+            let mut header = members(reserved.clone());
+            header.insert("name".to_string(), json!("a"));
+            let mut trailing = members(reserved);
+            trailing.insert("count".to_string(), json!(0));
+            let mut writer = FeatureWriter::from_writer(&mut buffer)
+                .with_bbox(vec![1.0, 2.0, 3.0, 4.0])
+                .with_foreign_members(header)
+                .with_trailing_members(trailing);
+            for name in ["type", "features", "bbox"] {
+                assert!(matches!(
+                    writer.insert_trailing_member(name, json!(1)),
+                    Err(Error::InvalidWriterState(_))
+                ));
+            }
+        }
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            r#"{ "type": "FeatureCollection", "bbox": [1.0,2.0,3.0,4.0], "name": "a", "features": [], "count": 0}"#
+        );
+    }
+
+    #[test]
+    fn pretty() {
+        let mut buffer: Vec<u8> = vec![];
+        {
+            FeatureWriter::from_writer(&mut buffer)
+                .pretty()
+                .with_bbox(vec![1.0, 2.0, 3.0, 4.0]);
+        }
+        let expected = r#"{
+  "type": "FeatureCollection",
+  "bbox": [
+    1.0,
+    2.0,
+    3.0,
+    4.0
+  ],
+  "features": []
+}"#;
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let mut writer = FeatureWriter::from_writer(&mut buffer).pretty();
+            writer
+                .serialize(&MyRecord {
+                    geometry: crate::Geometry::from(crate::Value::Point(vec![1.1, 1.2])),
+                    name: "Mishka".to_string(),
+                    age: 12,
+                })
+                .unwrap();
+            let lazy_feature = r#"{"type": "Feature", "geometry": null, "properties": null}"#;
+            let input = format!(
+                r#"{{"type": "FeatureCollection", "features": [{}]}}"#,
+                lazy_feature
+            );
+            for feature in crate::FeatureReader::from_reader(input.as_bytes()).lazy_features() {
+                writer.write_lazy_feature(&feature.unwrap()).unwrap();
+            }
+        }
+        let expected = r#"{
+  "type": "FeatureCollection",
+  "features": [
+    {
+      "type": "Feature",
+      "geometry": {
+        "coordinates": [
+          1.1,
+          1.2
+        ],
+        "type": "Point"
+      },
+      "properties": {
+        "age": 12,
+        "name": "Mishka"
+      }
+    },
+    {"type": "Feature", "geometry": null, "properties": null}
+  ]
+}"#;
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+    }
+
+    #[test]
+    fn computed_bbox() {
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let mut writer = FeatureWriter::from_writer(&mut buffer)
+                .with_bbox(vec![0.0, 0.0, 0.0, 0.0])
+                .with_computed_bbox();
+            writer
+                .write_feature(&Feature::from(crate::Value::LineString(vec![
+                    vec![1.0, 5.0, 10.0],
+                    vec![2.0, 6.0, -10.0],
+                ])))
+                .unwrap();
+            writer
+                .serialize(&MyRecord {
+                    geometry: crate::Geometry::from(crate::Value::Point(vec![-1.0, 3.0])),
+                    name: "Mishka".to_string(),
+                    age: 12,
+                })
+                .unwrap();
+            let lazy_feature = r#"{"type": "Feature", "geometry": {"type": "Point", "coordinates": [9, 9]}, "properties": null}"#;
+            let input = format!(
+                r#"{{"type": "FeatureCollection", "features": [{}]}}"#,
+                lazy_feature
+            );
+            for feature in crate::FeatureReader::from_reader(input.as_bytes()).lazy_features() {
+                writer.write_lazy_feature(&feature.unwrap()).unwrap();
+            }
+            writer.write_feature(&Feature::default()).unwrap();
+        }
+        // The points have no Z, so neither does the bbox.
+        let actual_json: JsonValue = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(actual_json["bbox"], json!([-1.0, 3.0, 9.0, 9.0]));
+
+        // Without positions there's no bbox.
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let mut writer = FeatureWriter::from_writer(&mut buffer).with_computed_bbox();
+            writer.write_feature(&Feature::default()).unwrap();
+        }
+        let actual_json: JsonValue = serde_json::from_slice(&buffer).unwrap();
+        assert!(actual_json.get("bbox").is_none());
+
+        // Measures aren't part of the bbox.
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let mut writer = FeatureWriter::from_writer(&mut buffer).with_computed_bbox();
+            writer
+                .serialize(&MyRecord {
+                    geometry: crate::Geometry::from(crate::Value::LineString(vec![
+                        vec![1.0, 5.0, 10.0, 0.0],
+                        vec![2.0, 6.0, -10.0, 100.0],
+                    ])),
+                    name: "Mishka".to_string(),
+                    age: 12,
+                })
+                .unwrap();
+        }
+        let actual_json: JsonValue = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(
+            actual_json["bbox"],
+            json!([1.0, 5.0, -10.0, 2.0, 6.0, 10.0])
+        );
+    }
+
+    #[test]
+    fn computed_bbox_invalid_geometry() {
+        #[derive(Serialize)]
+        struct Record {
+            geometry: JsonValue,
+        }
+
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let mut writer = FeatureWriter::from_writer(&mut buffer).with_computed_bbox();
+            writer.write_feature(&Feature::default()).unwrap();
+            let invalid = json!({ "type": "Point", "coordinates": [1.0] });
+            assert!(writer.serialize(&Record { geometry: invalid }).is_err());
+            let input = r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "geometry": {"type": "Point", "coordinates": []}, "properties": null}
+            ]}"#;
+            for feature in crate::FeatureReader::from_reader(input.as_bytes()).lazy_features() {
+                assert!(writer.write_lazy_feature(&feature.unwrap()).is_err());
+            }
+            writer.write_feature(&Feature::default()).unwrap();
+        }
+        // The invalid features weren't started, so the output is still valid.
+        let actual_json: JsonValue = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(actual_json["features"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn computed_bbox_in_header() {
+        fn write(
+            writer: impl FnOnce(
+                FeatureWriter<&mut io::Cursor<Vec<u8>>>,
+            ) -> FeatureWriter<&mut io::Cursor<Vec<u8>>>,
+            positions: &[Vec<f64>],
+        ) -> String {
+            let mut output = io::Cursor::new(vec![]);
+            {
+                let mut writer = writer(FeatureWriter::from_writer(&mut output));
+                for position in positions {
+                    writer
+                        .write_feature(&Feature::from(crate::Value::Point(position.clone())))
+                        .unwrap();
+                }
+            }
+            let output = String::from_utf8(output.into_inner()).unwrap();
+            // The output is always valid.
+            serde_json::from_str::<JsonValue>(&output).unwrap();
+            output
+        }
+        let spaces = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let positions = [vec![1.0, 2.0], vec![-1.0, 0.5]];
+        let feature = |x, y| {
+            format!(
+                r#"{{"geometry":{{"coordinates":[{:?},{:?}],"type":"Point"}},"properties":{{}},"type":"Feature"}}"#,
+                x, y
+            )
+        };
+        let features = format!("{},{}", feature(1.0, 2.0), feature(-1.0, 0.5));
+
+        let output = write(|writer| writer.with_computed_bbox_in_header(), &positions);
+        assert_eq!(
+            spaces(&output),
+            format!(
+                r#"{{ "type": "FeatureCollection", "bbox": [-1.0,0.5,1.0,2.0] , "features": [{}]}}"#,
+                features
+            )
+        );
+
+        let output = write(
+            |writer| {
+                writer
+                    .with_computed_bbox_in_header()
+                    .with_type_position(TypePosition::Last)
+            },
+            &positions,
+        );
+        assert_eq!(
+            spaces(&output),
+            format!(
+                r#"{{ "bbox": [-1.0,0.5,1.0,2.0], "features": [{}], "type": "FeatureCollection"}}"#,
+                features
+            )
+        );
+
+        let output = write(
+            |writer| {
+                writer
+                    .with_computed_bbox_in_header()
+                    .with_type_position(TypePosition::Last)
+            },
+            &[],
+        );
+        assert_eq!(
+            spaces(&output),
+            r#"{ "features": [], "type": "FeatureCollection"}"#
+        );
+
+        let output = write(
+            |writer| writer.pretty().with_computed_bbox_in_header(),
+            &positions[..1],
+        );
+        assert!(output.starts_with(
+            "{\n  \"type\": \"FeatureCollection\",\n  \"bbox\": [1.0,2.0,1.0,2.0]      "
+        ));
+
+        // Measures aren't part of the bbox, so even the longest numbers fit.
+        let output = write(
+            |writer| writer.with_computed_bbox_in_header(),
+            &[vec![-1e-300 / 3.0; 4]],
+        );
+        let value: JsonValue = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["bbox"].as_array().unwrap().len(), 6);
+        assert!(output.starts_with(r#"{ "type": "FeatureCollection", "bbox": ["#));
+    }
+
     #[cfg(feature = "geo-types")]
     mod test_geo_types {
         use super::*;
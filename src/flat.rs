@@ -0,0 +1,630 @@
+//! Geometries whose coordinates are stored in one flat buffer.
+//!
+//! A [`FlatValue`] stores all the coordinates of a geometry one after another in a single
+//! `Vec<f64>`, with the structure of the geometry given by arrays of offsets, like the
+//! [GeoArrow](https://geoarrow.org) and [FlatGeobuf](https://flatgeobuf.org) formats. The
+//! coordinates can be handed as they are to SIMD kernels or across FFI.
+//!
+//! Every position must have the same number of dimensions, and GeometryCollections can't be
+//! stored flat, so converting from a [`Value`] can fail. Converting back is lossless.
+use crate::compact::FlatValueDef;
+use crate::errors::{Error, Result};
+use crate::object_visitor::GeoJsonObject;
+use crate::{Position, Value};
+
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// The type of a [`FlatValue`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlatType {
+    Point,
+    MultiPoint,
+    LineString,
+    MultiLineString,
+    Polygon,
+    MultiPolygon,
+}
+
+impl FlatType {
+    pub fn name(self) -> &'static str {
+        match self {
+            FlatType::Point => "Point",
+            FlatType::MultiPoint => "MultiPoint",
+            FlatType::LineString => "LineString",
+            FlatType::MultiLineString => "MultiLineString",
+            FlatType::Polygon => "Polygon",
+            FlatType::MultiPolygon => "MultiPolygon",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "Point" => FlatType::Point,
+            "MultiPoint" => FlatType::MultiPoint,
+            "LineString" => FlatType::LineString,
+            "MultiLineString" => FlatType::MultiLineString,
+            "Polygon" => FlatType::Polygon,
+            "MultiPolygon" => FlatType::MultiPolygon,
+            _ => return None,
+        })
+    }
+
+    /// How deeply positions are nested in the `coordinates`.
+    pub(crate) fn depth(self) -> usize {
+        match self {
+            FlatType::Point => 0,
+            FlatType::MultiPoint | FlatType::LineString => 1,
+            FlatType::MultiLineString | FlatType::Polygon => 2,
+            FlatType::MultiPolygon => 3,
+        }
+    }
+}
+
+/// A geometry whose coordinates are stored in one flat buffer.
+///
+/// - [`coordinates`](FlatValue::coordinates) holds the values of every position, one position
+///   after another, each with [`dimensions`](FlatValue::dimensions) values.
+/// - For a MultiLineString or Polygon, [`ring_offsets`](FlatValue::ring_offsets) holds the index
+///   of the first position of each line or ring, followed by the number of positions.
+/// - For a MultiPolygon, [`ring_offsets`](FlatValue::ring_offsets) holds the same for every ring
+///   of every polygon, and [`polygon_offsets`](FlatValue::polygon_offsets) holds the index of the
+///   first ring of each polygon, followed by the number of rings.
+///
+/// Other offsets are empty.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{FlatType, FlatValue, Value};
+/// use std::convert::TryFrom;
+///
+/// let value = Value::MultiLineString(vec![
+///     vec![vec![1.0, 2.0], vec![3.0, 4.0]],
+///     vec![vec![5.0, 6.0], vec![7.0, 8.0], vec![9.0, 10.0]],
+/// ]);
+/// let flat = FlatValue::try_from(&value).unwrap();
+/// assert_eq!(flat.flat_type(), FlatType::MultiLineString);
+/// assert_eq!(flat.dimensions(), 2);
+/// assert_eq!(
+///     flat.coordinates(),
+///     [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0]
+/// );
+/// assert_eq!(flat.ring_offsets(), [0, 2, 5]);
+/// assert_eq!(Value::from(flat), value);
+///
+/// // It can also be parsed straight from GeoJSON.
+/// let flat: FlatValue = r#"{"type": "Point", "coordinates": [1, 2, 3]}"#.parse().unwrap();
+/// assert_eq!(flat.dimensions(), 3);
+/// assert_eq!(flat.to_string(), r#"{"coordinates":[1.0,2.0,3.0],"type":"Point"}"#);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlatValue {
+    pub(crate) flat_type: FlatType,
+    pub(crate) dimensions: usize,
+    pub(crate) coordinates: Vec<f64>,
+    pub(crate) ring_offsets: Vec<usize>,
+    pub(crate) polygon_offsets: Vec<usize>,
+}
+
+impl FlatValue {
+    /// Assemble a `FlatValue` from its parts, checking that they're consistent.
+    pub fn from_parts(
+        flat_type: FlatType,
+        dimensions: usize,
+        coordinates: Vec<f64>,
+        ring_offsets: Vec<usize>,
+        polygon_offsets: Vec<usize>,
+    ) -> Result<Self> {
+        let value = FlatValue {
+            flat_type,
+            dimensions,
+            coordinates,
+            ring_offsets,
+            polygon_offsets,
+        };
+        value.check()?;
+        Ok(value)
+    }
+
+    pub fn flat_type(&self) -> FlatType {
+        self.flat_type
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.flat_type.name()
+    }
+
+    /// The number of values in each position.
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    pub fn coordinates(&self) -> &[f64] {
+        &self.coordinates
+    }
+
+    /// The coordinates, to change them in place.
+    pub fn coordinates_mut(&mut self) -> &mut [f64] {
+        &mut self.coordinates
+    }
+
+    pub fn ring_offsets(&self) -> &[usize] {
+        &self.ring_offsets
+    }
+
+    pub fn polygon_offsets(&self) -> &[usize] {
+        &self.polygon_offsets
+    }
+
+    pub fn num_positions(&self) -> usize {
+        self.coordinates.len() / self.dimensions
+    }
+
+    /// The positions, as slices of the coordinates.
+    pub fn positions(&self) -> std::slice::ChunksExact<'_, f64> {
+        self.coordinates.chunks_exact(self.dimensions)
+    }
+
+    /// Take the parts of the `FlatValue`, in the order of [`FlatValue::from_parts`].
+    pub fn into_parts(self) -> (FlatType, usize, Vec<f64>, Vec<usize>, Vec<usize>) {
+        (
+            self.flat_type,
+            self.dimensions,
+            self.coordinates,
+            self.ring_offsets,
+            self.polygon_offsets,
+        )
+    }
+
+    pub(crate) fn check(&self) -> Result<()> {
+        let invalid = |message: String| Err(Error::InvalidFlatValue(message));
+        if self.dimensions < 2 {
+            return invalid(format!(
+                "positions must have at least 2 dimensions, not {}",
+                self.dimensions
+            ));
+        }
+        if self.num_positions() * self.dimensions != self.coordinates.len() {
+            return invalid(format!(
+                "{} coordinates aren't a whole number of {}-dimensional positions",
+                self.coordinates.len(),
+                self.dimensions
+            ));
+        }
+        if self.flat_type == FlatType::Point && self.num_positions() != 1 {
+            return invalid(format!(
+                "a Point must have 1 position, not {}",
+                self.num_positions()
+            ));
+        }
+        let (has_rings, has_polygons) = match self.flat_type.depth() {
+            0 | 1 => (false, false),
+            2 => (true, false),
+            _ => (true, true),
+        };
+        check_offsets("ring", &self.ring_offsets, has_rings, self.num_positions())?;
+        let num_rings = self.ring_offsets.len().saturating_sub(1);
+        check_offsets("polygon", &self.polygon_offsets, has_polygons, num_rings)
+    }
+
+    /// The range of positions of ring `index`.
+    fn ring(&self, index: usize) -> Range<usize> {
+        self.ring_offsets[index]..self.ring_offsets[index + 1]
+    }
+
+    /// The range of rings of polygon `index`.
+    fn polygon(&self, index: usize) -> Range<usize> {
+        self.polygon_offsets[index]..self.polygon_offsets[index + 1]
+    }
+
+    fn position(&self, index: usize) -> &[f64] {
+        &self.coordinates[index * self.dimensions..(index + 1) * self.dimensions]
+    }
+
+    fn to_positions(&self, positions: Range<usize>) -> Vec<Position> {
+        positions
+            .map(|index| self.position(index).to_vec())
+            .collect()
+    }
+
+    fn to_rings(&self, rings: Range<usize>) -> Vec<Vec<Position>> {
+        rings
+            .map(|index| self.to_positions(self.ring(index)))
+            .collect()
+    }
+}
+
+fn check_offsets(name: &str, offsets: &[usize], expected: bool, end: usize) -> Result<()> {
+    let invalid = |message: String| Err(Error::InvalidFlatValue(message));
+    if !expected {
+        if !offsets.is_empty() {
+            return invalid(format!("unexpected {} offsets", name));
+        }
+        return Ok(());
+    }
+    if offsets.first() != Some(&0) {
+        return invalid(format!("{} offsets must start at 0", name));
+    }
+    if offsets.windows(2).any(|pair| pair[0] > pair[1]) {
+        return invalid(format!("{} offsets must not decrease", name));
+    }
+    if offsets.last() != Some(&end) {
+        return invalid(format!("{} offsets must end at {}", name, end));
+    }
+    Ok(())
+}
+
+/// Builds a [`FlatValue`] from positions given in order, and the ends of the arrays holding them.
+pub(crate) struct FlatBuilder {
+    value: FlatValue,
+    /// Whether the number of dimensions has been set by a position yet.
+    has_dimensions: bool,
+}
+
+impl FlatBuilder {
+    pub(crate) fn new(flat_type: FlatType) -> Self {
+        let depth = flat_type.depth();
+        FlatBuilder {
+            value: FlatValue {
+                flat_type,
+                dimensions: 2,
+                coordinates: vec![],
+                ring_offsets: if depth >= 2 { vec![0] } else { vec![] },
+                polygon_offsets: if depth >= 3 { vec![0] } else { vec![] },
+            },
+            has_dimensions: false,
+        }
+    }
+
+    pub(crate) fn push_position(&mut self, position: &[f64]) -> Result<()> {
+        if !self.has_dimensions {
+            self.value.dimensions = position.len();
+            self.has_dimensions = true;
+        } else if position.len() != self.value.dimensions {
+            return Err(Error::InvalidFlatValue(format!(
+                "every position must have {} dimensions, like the first, not {}",
+                self.value.dimensions,
+                position.len()
+            )));
+        }
+        self.value.coordinates.extend_from_slice(position);
+        Ok(())
+    }
+
+    /// End an array within the `coordinates`, which holds positions `levels` arrays deep, i.e. 1
+    /// for a ring and 2 for a polygon.
+    pub(crate) fn end_array(&mut self, levels: usize) {
+        let value = &mut self.value;
+        if levels >= value.flat_type.depth() {
+            // The `coordinates` themselves.
+            return;
+        }
+        match levels {
+            1 => {
+                let num_positions = value.coordinates.len() / value.dimensions;
+                value.ring_offsets.push(num_positions);
+            }
+            2 => {
+                let num_rings = value.ring_offsets.len() - 1;
+                value.polygon_offsets.push(num_rings);
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<FlatValue> {
+        self.value.check()?;
+        Ok(self.value)
+    }
+}
+
+impl TryFrom<&Value> for FlatValue {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self> {
+        let flat_type = match value {
+            Value::Point(_) => FlatType::Point,
+            Value::MultiPoint(_) => FlatType::MultiPoint,
+            Value::LineString(_) => FlatType::LineString,
+            Value::MultiLineString(_) => FlatType::MultiLineString,
+            Value::Polygon(_) => FlatType::Polygon,
+            Value::MultiPolygon(_) => FlatType::MultiPolygon,
+            Value::GeometryCollection(_) => {
+                return Err(Error::InvalidFlatValue(
+                    "a GeometryCollection can't be stored flat".to_string(),
+                ))
+            }
+        };
+        let mut builder = FlatBuilder::new(flat_type);
+        let push_positions = |builder: &mut FlatBuilder, positions: &[Position]| -> Result<()> {
+            for position in positions {
+                builder.push_position(position)?;
+            }
+            builder.end_array(1);
+            Ok(())
+        };
+        let push_rings = |builder: &mut FlatBuilder, rings: &[Vec<Position>]| -> Result<()> {
+            for ring in rings {
+                push_positions(builder, ring)?;
+            }
+            builder.end_array(2);
+            Ok(())
+        };
+        match value {
+            Value::Point(position) => builder.push_position(position)?,
+            Value::MultiPoint(positions) | Value::LineString(positions) => {
+                push_positions(&mut builder, positions)?
+            }
+            Value::MultiLineString(rings) | Value::Polygon(rings) => {
+                push_rings(&mut builder, rings)?
+            }
+            Value::MultiPolygon(polygons) => {
+                for rings in polygons {
+                    push_rings(&mut builder, rings)?;
+                }
+            }
+            Value::GeometryCollection(_) => unreachable!(),
+        }
+        builder.finish()
+    }
+}
+
+impl TryFrom<Value> for FlatValue {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        FlatValue::try_from(&value)
+    }
+}
+
+impl From<&FlatValue> for Value {
+    fn from(flat: &FlatValue) -> Self {
+        let num_rings = flat.ring_offsets.len().saturating_sub(1);
+        let num_polygons = flat.polygon_offsets.len().saturating_sub(1);
+        match flat.flat_type {
+            FlatType::Point => Value::Point(flat.position(0).to_vec()),
+            FlatType::MultiPoint => Value::MultiPoint(flat.to_positions(0..flat.num_positions())),
+            FlatType::LineString => Value::LineString(flat.to_positions(0..flat.num_positions())),
+            FlatType::MultiLineString => Value::MultiLineString(flat.to_rings(0..num_rings)),
+            FlatType::Polygon => Value::Polygon(flat.to_rings(0..num_rings)),
+            FlatType::MultiPolygon => Value::MultiPolygon(
+                (0..num_polygons)
+                    .map(|index| flat.to_rings(flat.polygon(index)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<FlatValue> for Value {
+    fn from(flat: FlatValue) -> Self {
+        Value::from(&flat)
+    }
+}
+
+impl FromStr for FlatValue {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+impl fmt::Display for FlatValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ::serde_json::to_string(self)
+            .map_err(|_| fmt::Error)
+            .and_then(|s| f.write_str(&s))
+    }
+}
+
+/// Nested arrays of the positions of a [`FlatValue`].
+enum Nested<'a> {
+    Positions(&'a FlatValue, Range<usize>),
+    Rings(&'a FlatValue, Range<usize>),
+    Polygons(&'a FlatValue, Range<usize>),
+}
+
+impl Serialize for Nested<'_> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Nested::Positions(flat, positions) => {
+                serializer.collect_seq(positions.clone().map(|index| flat.position(index)))
+            }
+            Nested::Rings(flat, rings) => {
+                let mut seq = serializer.serialize_seq(Some(rings.len()))?;
+                for index in rings.clone() {
+                    seq.serialize_element(&Nested::Positions(flat, flat.ring(index)))?;
+                }
+                seq.end()
+            }
+            Nested::Polygons(flat, polygons) => {
+                let mut seq = serializer.serialize_seq(Some(polygons.len()))?;
+                for index in polygons.clone() {
+                    seq.serialize_element(&Nested::Rings(flat, flat.polygon(index)))?;
+                }
+                seq.end()
+            }
+        }
+    }
+}
+
+/// Serialized as a GeoJSON geometry object, without converting to nested vectors.
+impl Serialize for FlatValue {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return FlatValueDef::serialize(self, serializer);
+        }
+        let num_rings = self.ring_offsets.len().saturating_sub(1);
+        let num_polygons = self.polygon_offsets.len().saturating_sub(1);
+        let mut map = serializer.serialize_map(Some(2))?;
+        match self.flat_type {
+            FlatType::Point => map.serialize_entry("coordinates", self.position(0))?,
+            FlatType::MultiPoint | FlatType::LineString => map.serialize_entry(
+                "coordinates",
+                &Nested::Positions(self, 0..self.num_positions()),
+            )?,
+            FlatType::MultiLineString | FlatType::Polygon => {
+                map.serialize_entry("coordinates", &Nested::Rings(self, 0..num_rings))?
+            }
+            FlatType::MultiPolygon => {
+                map.serialize_entry("coordinates", &Nested::Polygons(self, 0..num_polygons))?
+            }
+        }
+        map.serialize_entry("type", self.type_name())?;
+        map.end()
+    }
+}
+
+/// Parsed from a GeoJSON geometry object, straight into the flat buffer.
+impl<'de> Deserialize<'de> for FlatValue {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error as SerdeError;
+
+        if !deserializer.is_human_readable() {
+            let value = FlatValueDef::deserialize(deserializer)?;
+            value.check().map_err(|e| D::Error::custom(e.to_string()))?;
+            return Ok(value);
+        }
+
+        GeoJsonObject::deserialize(deserializer)?
+            .into_flat_value()
+            .map_err(|e| de::Error::custom(e.without_location()))
+    }
+}
+
+impl FlatType {
+    /// The type of a GeoJSON object, or an error if it can't be stored flat.
+    pub(crate) fn from_type(type_: String) -> Result<Self> {
+        match FlatType::from_name(&type_) {
+            Some(flat_type) => Ok(flat_type),
+            None if type_ == "GeometryCollection" => Err(Error::InvalidFlatValue(
+                "a GeometryCollection can't be stored flat".to_string(),
+            )),
+            None => Err(Error::GeometryUnknownType(type_)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> Vec<Value> {
+        vec![
+            Value::Point(vec![1.0, 2.0, 3.0]),
+            Value::MultiPoint(vec![]),
+            Value::LineString(vec![vec![1.0, 2.0], vec![3.0, 4.0]]),
+            Value::Polygon(vec![
+                vec![
+                    vec![0.0, 0.0],
+                    vec![4.0, 0.0],
+                    vec![4.0, 4.0],
+                    vec![0.0, 0.0],
+                ],
+                vec![],
+                vec![
+                    vec![1.0, 1.0],
+                    vec![2.0, 1.0],
+                    vec![2.0, 2.0],
+                    vec![1.0, 1.0],
+                ],
+            ]),
+            Value::MultiPolygon(vec![
+                vec![vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![0.0, 0.0]]],
+                vec![],
+                vec![
+                    vec![vec![5.0, 5.0], vec![6.0, 5.0], vec![5.0, 5.0]],
+                    vec![vec![5.5, 5.1], vec![5.6, 5.1], vec![5.5, 5.1]],
+                ],
+            ]),
+        ]
+    }
+
+    #[test]
+    fn round_trip() {
+        for value in values() {
+            let flat = FlatValue::try_from(&value).unwrap();
+            assert_eq!(Value::from(&flat), value);
+
+            // Parsed and written as the same JSON as `Value`.
+            let json = value.to_string();
+            assert_eq!(flat.to_string(), json);
+            assert_eq!(json.parse::<FlatValue>().unwrap(), flat);
+
+            let bytes = bincode::serialize(&flat).unwrap();
+            assert_eq!(bincode::deserialize::<FlatValue>(&bytes).unwrap(), flat);
+        }
+    }
+
+    #[test]
+    fn offsets() {
+        let flat = FlatValue::try_from(&values()[4]).unwrap();
+        assert_eq!(flat.ring_offsets(), [0, 3, 6, 9]);
+        assert_eq!(flat.polygon_offsets(), [0, 1, 1, 3]);
+        assert_eq!(flat.num_positions(), 9);
+        assert_eq!(flat.positions().nth(3), Some(&[5.0, 5.0][..]));
+    }
+
+    #[test]
+    fn not_flat() {
+        let err = FlatValue::try_from(Value::LineString(vec![vec![1.0, 2.0], vec![1.0, 2.0, 3.0]]))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidFlatValue(_)));
+
+        let err = FlatValue::try_from(Value::GeometryCollection(vec![])).unwrap_err();
+        assert!(matches!(err, Error::InvalidFlatValue(_)));
+
+        let err = r#"{"type": "Polygon", "coordinates": [[[1, 2], [3, 4, 5]]]}"#
+            .parse::<FlatValue>()
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid flat geometry"));
+    }
+
+    #[test]
+    fn from_parts() {
+        let flat = FlatValue::from_parts(
+            FlatType::Polygon,
+            3,
+            vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            vec![0, 3],
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(
+            Value::from(flat),
+            Value::Polygon(vec![vec![
+                vec![0.0, 0.0, 0.0],
+                vec![1.0, 0.0, 0.0],
+                vec![0.0, 0.0, 0.0]
+            ]])
+        );
+
+        let parts = |ring_offsets| {
+            FlatValue::from_parts(FlatType::Polygon, 2, vec![0.0; 6], ring_offsets, vec![])
+        };
+        assert!(parts(vec![0, 3]).is_ok());
+        assert!(parts(vec![0, 2]).is_err());
+        assert!(parts(vec![1, 3]).is_err());
+        assert!(parts(vec![0, 2, 1, 3]).is_err());
+        assert!(FlatValue::from_parts(FlatType::Point, 2, vec![0.0; 3], vec![], vec![]).is_err());
+        assert!(
+            FlatValue::from_parts(FlatType::LineString, 2, vec![0.0; 4], vec![0, 2], vec![])
+                .is_err()
+        );
+    }
+}
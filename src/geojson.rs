@@ -12,8 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::compact::GeoJsonDef;
 use crate::errors::{Error, Result};
-use crate::{Feature, FeatureCollection, Geometry};
+use crate::object_visitor::GeoJsonObject;
+use crate::parse_options::LimitReader;
+use crate::{Bbox, Feature, FeatureCollection, Geometry, ParseOptions, WithOptions, WriteOptions};
 use crate::{JsonObject, JsonValue};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::convert::TryFrom;
@@ -233,6 +236,89 @@ impl GeoJson {
     {
         serde_json::from_reader(rdr)
     }
+
+    /// Deserialize a GeoJson object from an IO stream of JSON, rejecting input which exceeds the
+    /// limits of `options`, and reporting where errors were found if they ask for it.
+    ///
+    /// See [`ParseOptions`] for an example.
+    pub fn from_reader_with_options<R>(rdr: R, options: &ParseOptions) -> Result<Self>
+    where
+        R: std::io::Read,
+    {
+        let mut reader = LimitReader::new(rdr, options.clone());
+        let result = serde_json::from_reader::<_, GeoJsonObject>(&mut reader);
+        if let Some(err) = reader.scanner.error() {
+            return Err(err);
+        }
+        match result?.into_geojson() {
+            Err(err) if !options.error_locations => Err(err.into_unlocated()),
+            result => result,
+        }
+    }
+
+    /// Computes the bbox of the object from its coordinates, ignoring any `bbox`s present.
+    ///
+    /// See [`Geometry::compute_bbox`].
+    pub fn compute_bbox(&self) -> Option<Bbox> {
+        match self {
+            GeoJson::Geometry(geometry) => geometry.compute_bbox(),
+            GeoJson::Feature(feature) => feature.compute_bbox(),
+            GeoJson::FeatureCollection(collection) => collection.compute_bbox(),
+        }
+    }
+
+    /// Sets the `bbox` of the object to the [computed bbox](GeoJson::compute_bbox).
+    pub fn with_computed_bbox(self) -> Self {
+        match self {
+            GeoJson::Geometry(geometry) => GeoJson::Geometry(geometry.with_computed_bbox()),
+            GeoJson::Feature(feature) => GeoJson::Feature(feature.with_computed_bbox()),
+            GeoJson::FeatureCollection(collection) => {
+                GeoJson::FeatureCollection(collection.with_computed_bbox())
+            }
+        }
+    }
+
+    /// Sets the `bbox` of the object, and of every object within it, to its
+    /// [computed bbox](GeoJson::compute_bbox).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::GeoJson;
+    ///
+    /// let mut geojson: GeoJson = r#"{
+    ///     "type": "FeatureCollection",
+    ///     "features": [
+    ///         {
+    ///             "type": "Feature",
+    ///             "properties": null,
+    ///             "geometry": {"type": "Point", "coordinates": [1, 2]}
+    ///         },
+    ///         {
+    ///             "type": "Feature",
+    ///             "properties": null,
+    ///             "geometry": {"type": "Point", "coordinates": [3, 0]}
+    ///         }
+    ///     ]
+    /// }"#
+    /// .parse()
+    /// .unwrap();
+    /// geojson.fill_bboxes();
+    ///
+    /// if let GeoJson::FeatureCollection(collection) = &geojson {
+    ///     assert_eq!(collection.bbox, Some(vec![1.0, 0.0, 3.0, 2.0]));
+    ///     assert_eq!(collection.features[0].bbox, Some(vec![1.0, 2.0, 1.0, 2.0]));
+    ///     let geometry = collection.features[0].geometry.as_ref().unwrap();
+    ///     assert_eq!(geometry.bbox, Some(vec![1.0, 2.0, 1.0, 2.0]));
+    /// }
+    /// ```
+    pub fn fill_bboxes(&mut self) {
+        match self {
+            GeoJson::Geometry(geometry) => geometry.fill_bboxes(),
+            GeoJson::Feature(feature) => feature.fill_bboxes(),
+            GeoJson::FeatureCollection(collection) => collection.fill_bboxes(),
+        }
+    }
 }
 
 impl TryFrom<JsonObject> for GeoJson {
@@ -301,7 +387,29 @@ impl Serialize for GeoJson {
     where
         S: Serializer,
     {
-        JsonObject::from(self).serialize(serializer)
+        if !serializer.is_human_readable() {
+            return GeoJsonDef::serialize(self, serializer);
+        }
+        WithOptions::new(self, &WriteOptions::default()).serialize(serializer)
+    }
+}
+
+impl Serialize for WithOptions<'_, GeoJson> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.value {
+            GeoJson::Geometry(geometry) => {
+                WithOptions::new(geometry, self.options).serialize(serializer)
+            }
+            GeoJson::Feature(feature) => {
+                WithOptions::new(feature, self.options).serialize(serializer)
+            }
+            GeoJson::FeatureCollection(collection) => {
+                WithOptions::new(collection, self.options).serialize(serializer)
+            }
+        }
     }
 }
 
@@ -312,9 +420,13 @@ impl<'de> Deserialize<'de> for GeoJson {
     {
         use serde::de::Error as SerdeError;
 
-        let val = JsonObject::deserialize(deserializer)?;
+        if !deserializer.is_human_readable() {
+            return GeoJsonDef::deserialize(deserializer);
+        }
 
-        GeoJson::from_json_object(val).map_err(|e| D::Error::custom(e.to_string()))
+        GeoJsonObject::deserialize(deserializer)?
+            .into_geojson()
+            .map_err(|e| D::Error::custom(e.without_location()))
     }
 }
 
@@ -15,8 +15,12 @@
 use std::str::FromStr;
 use std::{convert::TryFrom, fmt};
 
+use crate::compact::GeometryDef;
 use crate::errors::{Error, Result};
-use crate::{util, Bbox, LineStringType, PointType, PolygonType};
+use crate::object_serializer::{geometry_members, serialize_object, ValueObject};
+use crate::object_visitor::GeoJsonObject;
+use crate::util::Extent;
+use crate::{util, Bbox, LineStringType, PointType, PolygonType, WithOptions, WriteOptions};
 use crate::{JsonObject, JsonValue};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -147,7 +151,7 @@ impl TryFrom<JsonValue> for Value {
 
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        ::serde_json::to_string(&JsonObject::from(self))
+        ::serde_json::to_string(&ValueObject(self))
             .map_err(|_| fmt::Error)
             .and_then(|s| f.write_str(&s))
     }
@@ -173,7 +177,15 @@ impl Serialize for Value {
     where
         S: Serializer,
     {
-        JsonValue::from(self).serialize(serializer)
+        match self {
+            Value::Point(x) => x.serialize(serializer),
+            Value::MultiPoint(x) => x.serialize(serializer),
+            Value::LineString(x) => x.serialize(serializer),
+            Value::MultiLineString(x) => x.serialize(serializer),
+            Value::Polygon(x) => x.serialize(serializer),
+            Value::MultiPolygon(x) => x.serialize(serializer),
+            Value::GeometryCollection(x) => x.serialize(serializer),
+        }
     }
 }
 
@@ -265,6 +277,65 @@ impl Geometry {
             foreign_members: None,
         }
     }
+
+    /// Computes the bbox of the geometry from its coordinates, ignoring any `bbox` it has.
+    ///
+    /// The bbox has a minimum and maximum of X, Y and, if every position has one, Z, across the
+    /// positions including those of the geometries of a GeometryCollection, or is `None` if there
+    /// are no positions. Any values after Z are measures, so aren't part of the bbox. Longitudes
+    /// are compared as plain numbers, so the bbox never crosses the antimeridian.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Geometry, Value};
+    ///
+    /// let geometry = Geometry::new(Value::LineString(vec![
+    ///     vec![1.0, 2.0, 3.0],
+    ///     vec![-1.0, 4.0, 0.0],
+    /// ]));
+    /// assert_eq!(geometry.compute_bbox(), Some(vec![-1.0, 2.0, 0.0, 1.0, 4.0, 3.0]));
+    ///
+    /// let geometry = geometry.with_computed_bbox();
+    /// assert_eq!(geometry.bbox, Some(vec![-1.0, 2.0, 0.0, 1.0, 4.0, 3.0]));
+    /// ```
+    pub fn compute_bbox(&self) -> Option<Bbox> {
+        self.extent().to_bbox()
+    }
+
+    /// Sets `bbox` to the [computed bbox](Geometry::compute_bbox).
+    pub fn with_computed_bbox(mut self) -> Self {
+        self.bbox = self.compute_bbox();
+        self
+    }
+
+    /// Sets `bbox` to the [computed bbox](Geometry::compute_bbox), and does the same for every
+    /// geometry of a GeometryCollection.
+    pub fn fill_bboxes(&mut self) {
+        self.fill_extent();
+    }
+
+    pub(crate) fn extent(&self) -> Extent {
+        let mut extent = Extent::default();
+        extent.add_value(&self.value);
+        extent
+    }
+
+    /// Like `fill_bboxes`, returning the extent so that it doesn't need to be computed again.
+    pub(crate) fn fill_extent(&mut self) -> Extent {
+        let extent = match &mut self.value {
+            Value::GeometryCollection(geometries) => {
+                let mut extent = Extent::default();
+                for geometry in geometries {
+                    extent.union(&geometry.fill_extent());
+                }
+                extent
+            }
+            _ => self.extent(),
+        };
+        self.bbox = extent.to_bbox();
+        extent
+    }
 }
 
 impl<'a> From<&'a Geometry> for JsonObject {
@@ -333,7 +404,27 @@ impl Serialize for Geometry {
     where
         S: Serializer,
     {
-        JsonObject::from(self).serialize(serializer)
+        if !serializer.is_human_readable() {
+            return GeometryDef::serialize(self, serializer);
+        }
+        WithOptions::new(self, &WriteOptions::default()).serialize(serializer)
+    }
+}
+
+impl Serialize for WithOptions<'_, Geometry> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return self.value.serialize(serializer);
+        }
+        serialize_object(
+            serializer,
+            geometry_members(&self.value.value, self.value.bbox.as_ref()),
+            self.value.foreign_members.as_ref(),
+            self.options,
+        )
     }
 }
 
@@ -344,9 +435,13 @@ impl<'de> Deserialize<'de> for Geometry {
     {
         use serde::de::Error as SerdeError;
 
-        let val = JsonObject::deserialize(deserializer)?;
+        if !deserializer.is_human_readable() {
+            return GeometryDef::deserialize(deserializer);
+        }
 
-        Geometry::from_json_object(val).map_err(|e| D::Error::custom(e.to_string()))
+        GeoJsonObject::deserialize(deserializer)?
+            .into_geometry()
+            .map_err(|e| D::Error::custom(e.without_location()))
     }
 }
 
@@ -361,8 +456,9 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::{Error, GeoJson, Geometry, JsonObject, Value};
+    use crate::{BoundingBox, Error, GeoJson, Geometry, JsonObject, Value};
     use serde_json::json;
+    use std::convert::TryFrom;
     use std::str::FromStr;
 
     fn encode(geometry: &Geometry) -> String {
@@ -545,4 +641,56 @@ mod tests {
             "A position must contain two or more elements, but got `1`"
         );
     }
+
+    #[test]
+    fn compute_bbox() {
+        let mut geometry = Geometry {
+            bbox: Some(vec![0.0, 0.0, 0.0, 0.0]),
+            ..Geometry::new(Value::GeometryCollection(vec![
+                Geometry::new(Value::Point(vec![1.0, 2.0])),
+                Geometry::new(Value::MultiPolygon(vec![vec![vec![
+                    vec![-1.0, 0.0, 5.0],
+                    vec![3.0, 0.0, 5.0],
+                    vec![3.0, 1.0, 6.0],
+                    vec![-1.0, 0.0, 5.0],
+                ]]])),
+                Geometry::new(Value::GeometryCollection(vec![])),
+            ]))
+        };
+        // The point has no Z, so neither does the bbox.
+        let bbox = vec![-1.0, 0.0, 3.0, 2.0];
+        assert_eq!(geometry.compute_bbox(), Some(bbox.clone()));
+        assert_eq!(
+            geometry.clone().with_computed_bbox().bbox,
+            Some(bbox.clone())
+        );
+
+        geometry.fill_bboxes();
+        assert_eq!(geometry.bbox, Some(bbox));
+        match &geometry.value {
+            Value::GeometryCollection(geometries) => {
+                assert_eq!(geometries[0].bbox, Some(vec![1.0, 2.0, 1.0, 2.0]));
+                assert_eq!(
+                    geometries[1].bbox,
+                    Some(vec![-1.0, 0.0, 5.0, 3.0, 1.0, 6.0])
+                );
+                assert_eq!(geometries[2].bbox, None);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn compute_bbox_ignores_measures() {
+        let geometry = Geometry::new(Value::LineString(vec![
+            vec![1.0, 2.0, 3.0, 100.0],
+            vec![-1.0, 4.0, 0.0, -100.0],
+        ]));
+        let bbox = geometry.compute_bbox().unwrap();
+        assert_eq!(bbox, vec![-1.0, 2.0, 0.0, 1.0, 4.0, 3.0]);
+        assert_eq!(
+            BoundingBox::try_from(bbox).unwrap(),
+            BoundingBox::new_3d(-1.0, 2.0, 0.0, 1.0, 4.0, 3.0)
+        );
+    }
 }
@@ -0,0 +1,552 @@
+//! Geometries whose positions are stored inline.
+//!
+//! A [`Position`] is a `Vec<f64>`, so every position of a [`Geometry`] is a heap allocation of its
+//! own, and a polygon with a million vertices takes a million allocations to parse, clone and
+//! drop. [`InlineGeometry`] and [`InlineValue`] mirror [`Geometry`] and [`Value`], but hold
+//! [`InlinePosition`]s, which store positions of up to three dimensions inline and only allocate
+//! for positions with more.
+//!
+//! They're parsed and written as the same JSON as the usual types, and convert to and from them.
+use crate::compact::InlineGeometryDef;
+use crate::errors::{Error, Result};
+use crate::object_serializer::{geometry_members, serialize_object};
+use crate::object_visitor::GeoJsonObject;
+use crate::{Bbox, Geometry, JsonObject, Position, Value, WithOptions, WriteOptions};
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+/// The number of values an [`InlinePosition`] can store without allocating.
+const INLINE_LEN: usize = 3;
+
+/// A position which stores up to three values inline, and more on the heap.
+///
+/// It dereferences to a slice of its values.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::InlinePosition;
+///
+/// let position = InlinePosition::from([1.0, 2.0]);
+/// assert_eq!(position.len(), 2);
+/// assert_eq!(position[1], 2.0);
+/// assert!(position.is_inline());
+///
+/// let position = InlinePosition::from(vec![1.0, 2.0, 3.0, 4.0]);
+/// assert!(!position.is_inline());
+/// assert_eq!(Vec::from(position), vec![1.0, 2.0, 3.0, 4.0]);
+/// ```
+#[derive(Clone)]
+pub struct InlinePosition(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Inline { len: u8, values: [f64; INLINE_LEN] },
+    Spilled(Vec<f64>),
+}
+
+impl InlinePosition {
+    /// An empty position, to `push` values onto.
+    pub fn new() -> Self {
+        InlinePosition(Repr::Inline {
+            len: 0,
+            values: [0.0; INLINE_LEN],
+        })
+    }
+
+    /// Append a value, moving the values to the heap if there's no more room inline.
+    pub fn push(&mut self, value: f64) {
+        match &mut self.0 {
+            Repr::Inline { len, values } if (*len as usize) < INLINE_LEN => {
+                values[*len as usize] = value;
+                *len += 1;
+            }
+            Repr::Inline { values, .. } => {
+                let mut spilled = Vec::with_capacity(INLINE_LEN + 1);
+                spilled.extend_from_slice(values);
+                spilled.push(value);
+                self.0 = Repr::Spilled(spilled);
+            }
+            Repr::Spilled(values) => values.push(value),
+        }
+    }
+
+    /// Whether the values are stored inline, rather than on the heap.
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+
+    pub fn as_slice(&self) -> &[f64] {
+        match &self.0 {
+            Repr::Inline { len, values } => &values[..*len as usize],
+            Repr::Spilled(values) => values,
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [f64] {
+        match &mut self.0 {
+            Repr::Inline { len, values } => &mut values[..*len as usize],
+            Repr::Spilled(values) => values,
+        }
+    }
+}
+
+impl Default for InlinePosition {
+    fn default() -> Self {
+        InlinePosition::new()
+    }
+}
+
+impl Deref for InlinePosition {
+    type Target = [f64];
+
+    fn deref(&self) -> &[f64] {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for InlinePosition {
+    fn deref_mut(&mut self) -> &mut [f64] {
+        self.as_mut_slice()
+    }
+}
+
+impl fmt::Debug for InlinePosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl PartialEq for InlinePosition {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl FromIterator<f64> for InlinePosition {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut position = InlinePosition::new();
+        iter.into_iter().for_each(|value| position.push(value));
+        position
+    }
+}
+
+impl From<&[f64]> for InlinePosition {
+    fn from(values: &[f64]) -> Self {
+        if values.len() > INLINE_LEN {
+            return InlinePosition(Repr::Spilled(values.to_vec()));
+        }
+        values.iter().copied().collect()
+    }
+}
+
+/// Reuses the allocation of a position which doesn't fit inline.
+impl From<Vec<f64>> for InlinePosition {
+    fn from(values: Vec<f64>) -> Self {
+        if values.len() > INLINE_LEN {
+            return InlinePosition(Repr::Spilled(values));
+        }
+        InlinePosition::from(values.as_slice())
+    }
+}
+
+impl From<[f64; 2]> for InlinePosition {
+    fn from(values: [f64; 2]) -> Self {
+        InlinePosition::from(&values[..])
+    }
+}
+
+impl From<[f64; 3]> for InlinePosition {
+    fn from(values: [f64; 3]) -> Self {
+        InlinePosition::from(&values[..])
+    }
+}
+
+impl From<InlinePosition> for Vec<f64> {
+    fn from(position: InlinePosition) -> Self {
+        match position.0 {
+            Repr::Inline { .. } => position.as_slice().to_vec(),
+            Repr::Spilled(values) => values,
+        }
+    }
+}
+
+impl Serialize for InlinePosition {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for InlinePosition {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PositionVisitor;
+
+        impl<'de> Visitor<'de> for PositionVisitor {
+            type Value = InlinePosition;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an array of numbers")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut position = InlinePosition::new();
+                while let Some(value) = seq.next_element()? {
+                    position.push(value);
+                }
+                Ok(position)
+            }
+        }
+
+        deserializer.deserialize_seq(PositionVisitor)
+    }
+}
+
+/// A [`Value`] whose positions are [`InlinePosition`]s.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InlineValue {
+    Point(InlinePosition),
+    MultiPoint(Vec<InlinePosition>),
+    LineString(Vec<InlinePosition>),
+    MultiLineString(Vec<Vec<InlinePosition>>),
+    Polygon(Vec<Vec<InlinePosition>>),
+    MultiPolygon(Vec<Vec<Vec<InlinePosition>>>),
+    GeometryCollection(Vec<InlineGeometry>),
+}
+
+impl InlineValue {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            InlineValue::Point(..) => "Point",
+            InlineValue::MultiPoint(..) => "MultiPoint",
+            InlineValue::LineString(..) => "LineString",
+            InlineValue::MultiLineString(..) => "MultiLineString",
+            InlineValue::Polygon(..) => "Polygon",
+            InlineValue::MultiPolygon(..) => "MultiPolygon",
+            InlineValue::GeometryCollection(..) => "GeometryCollection",
+        }
+    }
+}
+
+fn convert_1d<P, Q: From<P>>(positions: Vec<P>) -> Vec<Q> {
+    positions.into_iter().map(Q::from).collect()
+}
+
+fn convert_2d<P, Q: From<P>>(positions: Vec<Vec<P>>) -> Vec<Vec<Q>> {
+    positions.into_iter().map(convert_1d).collect()
+}
+
+fn convert_3d<P, Q: From<P>>(positions: Vec<Vec<Vec<P>>>) -> Vec<Vec<Vec<Q>>> {
+    positions.into_iter().map(convert_2d).collect()
+}
+
+fn inline_1d(positions: &[Position]) -> Vec<InlinePosition> {
+    positions
+        .iter()
+        .map(|position| InlinePosition::from(position.as_slice()))
+        .collect()
+}
+
+fn inline_2d(positions: &[Vec<Position>]) -> Vec<Vec<InlinePosition>> {
+    positions
+        .iter()
+        .map(|positions| inline_1d(positions))
+        .collect()
+}
+
+impl From<Value> for InlineValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Point(x) => InlineValue::Point(x.into()),
+            Value::MultiPoint(x) => InlineValue::MultiPoint(convert_1d(x)),
+            Value::LineString(x) => InlineValue::LineString(convert_1d(x)),
+            Value::MultiLineString(x) => InlineValue::MultiLineString(convert_2d(x)),
+            Value::Polygon(x) => InlineValue::Polygon(convert_2d(x)),
+            Value::MultiPolygon(x) => InlineValue::MultiPolygon(convert_3d(x)),
+            Value::GeometryCollection(x) => InlineValue::GeometryCollection(convert_1d(x)),
+        }
+    }
+}
+
+impl From<&Value> for InlineValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Point(x) => InlineValue::Point(x.as_slice().into()),
+            Value::MultiPoint(x) => InlineValue::MultiPoint(inline_1d(x)),
+            Value::LineString(x) => InlineValue::LineString(inline_1d(x)),
+            Value::MultiLineString(x) => InlineValue::MultiLineString(inline_2d(x)),
+            Value::Polygon(x) => InlineValue::Polygon(inline_2d(x)),
+            Value::MultiPolygon(x) => {
+                InlineValue::MultiPolygon(x.iter().map(|x| inline_2d(x)).collect())
+            }
+            Value::GeometryCollection(x) => {
+                InlineValue::GeometryCollection(x.iter().map(InlineGeometry::from).collect())
+            }
+        }
+    }
+}
+
+impl From<InlineValue> for Value {
+    fn from(value: InlineValue) -> Self {
+        match value {
+            InlineValue::Point(x) => Value::Point(x.into()),
+            InlineValue::MultiPoint(x) => Value::MultiPoint(convert_1d(x)),
+            InlineValue::LineString(x) => Value::LineString(convert_1d(x)),
+            InlineValue::MultiLineString(x) => Value::MultiLineString(convert_2d(x)),
+            InlineValue::Polygon(x) => Value::Polygon(convert_2d(x)),
+            InlineValue::MultiPolygon(x) => Value::MultiPolygon(convert_3d(x)),
+            InlineValue::GeometryCollection(x) => Value::GeometryCollection(convert_1d(x)),
+        }
+    }
+}
+
+impl Serialize for InlineValue {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            InlineValue::Point(x) => x.serialize(serializer),
+            InlineValue::MultiPoint(x) => x.serialize(serializer),
+            InlineValue::LineString(x) => x.serialize(serializer),
+            InlineValue::MultiLineString(x) => x.serialize(serializer),
+            InlineValue::Polygon(x) => x.serialize(serializer),
+            InlineValue::MultiPolygon(x) => x.serialize(serializer),
+            InlineValue::GeometryCollection(x) => x.serialize(serializer),
+        }
+    }
+}
+
+/// A [`Geometry`] whose positions are [`InlinePosition`]s.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Geometry, InlineGeometry, InlineValue, Value};
+///
+/// let geometry: InlineGeometry = r#"{"type": "LineString", "coordinates": [[1, 2], [3, 4]]}"#
+///     .parse()
+///     .unwrap();
+/// match &geometry.value {
+///     InlineValue::LineString(positions) => assert!(positions[1].is_inline()),
+///     _ => unreachable!(),
+/// }
+///
+/// let geometry = Geometry::from(geometry);
+/// assert_eq!(
+///     geometry.value,
+///     Value::LineString(vec![vec![1.0, 2.0], vec![3.0, 4.0]])
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct InlineGeometry {
+    pub bbox: Option<Bbox>,
+    pub value: InlineValue,
+    pub foreign_members: Option<JsonObject>,
+}
+
+impl InlineGeometry {
+    pub fn new(value: InlineValue) -> Self {
+        InlineGeometry {
+            bbox: None,
+            value,
+            foreign_members: None,
+        }
+    }
+}
+
+impl From<InlineValue> for InlineGeometry {
+    fn from(value: InlineValue) -> Self {
+        InlineGeometry::new(value)
+    }
+}
+
+impl From<Geometry> for InlineGeometry {
+    fn from(geometry: Geometry) -> Self {
+        InlineGeometry {
+            bbox: geometry.bbox,
+            value: geometry.value.into(),
+            foreign_members: geometry.foreign_members,
+        }
+    }
+}
+
+impl From<&Geometry> for InlineGeometry {
+    fn from(geometry: &Geometry) -> Self {
+        InlineGeometry {
+            bbox: geometry.bbox.clone(),
+            value: InlineValue::from(&geometry.value),
+            foreign_members: geometry.foreign_members.clone(),
+        }
+    }
+}
+
+impl From<InlineGeometry> for Geometry {
+    fn from(geometry: InlineGeometry) -> Self {
+        Geometry {
+            bbox: geometry.bbox,
+            value: geometry.value.into(),
+            foreign_members: geometry.foreign_members,
+        }
+    }
+}
+
+impl FromStr for InlineGeometry {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+impl fmt::Display for InlineGeometry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ::serde_json::to_string(self)
+            .map_err(|_| fmt::Error)
+            .and_then(|s| f.write_str(&s))
+    }
+}
+
+impl Serialize for InlineGeometry {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return InlineGeometryDef::serialize(self, serializer);
+        }
+        WithOptions::new(self, &WriteOptions::default()).serialize(serializer)
+    }
+}
+
+impl Serialize for WithOptions<'_, InlineGeometry> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return self.value.serialize(serializer);
+        }
+        serialize_object(
+            serializer,
+            geometry_members(&self.value.value, self.value.bbox.as_ref()),
+            self.value.foreign_members.as_ref(),
+            self.options,
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for InlineGeometry {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return InlineGeometryDef::deserialize(deserializer);
+        }
+
+        GeoJsonObject::deserialize(deserializer)?
+            .into_inline_geometry()
+            .map_err(|e| de::Error::custom(e.without_location()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn geometry() -> Geometry {
+        Geometry {
+            bbox: Some(vec![1.0, 2.0, 3.0, 4.0]),
+            value: Value::GeometryCollection(vec![
+                Geometry::new(Value::Point(vec![1.0, 2.0])),
+                Geometry::new(Value::MultiPolygon(vec![vec![vec![
+                    vec![1.0, 2.0, 3.0],
+                    vec![3.0, 2.0, 1.0, 0.5],
+                    vec![3.0, 4.0],
+                    vec![1.0, 2.0, 3.0],
+                ]]])),
+            ]),
+            foreign_members: json!({ "name": "a" }).as_object().cloned(),
+        }
+    }
+
+    #[test]
+    fn push() {
+        let mut position = InlinePosition::new();
+        assert!(position.is_empty());
+        for value in 1..=3 {
+            position.push(value as f64);
+            assert!(position.is_inline());
+        }
+        position.push(4.0);
+        assert!(!position.is_inline());
+        assert_eq!(position.as_slice(), [1.0, 2.0, 3.0, 4.0]);
+        position[3] = 5.0;
+        assert_eq!(position, InlinePosition::from(vec![1.0, 2.0, 3.0, 5.0]));
+    }
+
+    #[test]
+    fn conversions() {
+        let geometry = geometry();
+        let inline = InlineGeometry::from(&geometry);
+        assert_eq!(inline, InlineGeometry::from(geometry.clone()));
+        assert_eq!(Geometry::from(inline), geometry);
+    }
+
+    #[test]
+    fn json() {
+        let geometry = geometry();
+        let inline = InlineGeometry::from(&geometry);
+        assert_eq!(inline.to_string(), geometry.to_string());
+        assert_eq!(
+            geometry.to_string().parse::<InlineGeometry>().unwrap(),
+            inline
+        );
+
+        let err = r#"{"type": "Point", "coordinates": [1]}"#.parse::<InlineGeometry>().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("A position must contain two or more elements"));
+    }
+
+    #[test]
+    fn round_coordinates() {
+        let inline = InlineGeometry::new(InlineValue::LineString(vec![
+            InlinePosition::from([1.23456, 2.0]),
+            InlinePosition::from(vec![1.0, 2.0, 3.0, 4.56789]),
+        ]));
+        let options = WriteOptions {
+            coordinate_precision: Some(2),
+            ..WriteOptions::default()
+        };
+        assert_eq!(
+            WithOptions::new(&inline, &options).to_string(),
+            r#"{"coordinates":[[1.23,2.0],[1.0,2.0,3.0,4.57]],"type":"LineString"}"#
+        );
+    }
+
+    #[test]
+    fn binary() {
+        let inline = InlineGeometry::from(geometry());
+        let bytes = bincode::serialize(&inline).unwrap();
+        assert_eq!(
+            bincode::deserialize::<InlineGeometry>(&bytes).unwrap(),
+            inline
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use crate::errors::{Error, Result};
+use crate::{BorrowedFeature, Feature, Geometry, JsonValue};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::value::RawValue;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// A Feature which keeps its JSON text, and only parses its geometry and properties when they're
+/// accessed.
+///
+/// Reading a LazyFeature only checks the structure of the Feature and records where its geometry
+/// and each property value are, so jobs which only look at a few members of each Feature don't
+/// pay to parse the rest. The original text is kept, so an unmodified LazyFeature can be written
+/// out byte-for-byte with [`FeatureWriter::write_lazy_feature`](crate::FeatureWriter::write_lazy_feature).
+///
+/// LazyFeatures can be read from a FeatureCollection with
+/// [`FeatureReader::lazy_features`](crate::FeatureReader::lazy_features), or deserialized from any
+/// JSON input with `serde_json`.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::LazyFeature;
+///
+/// let feature: LazyFeature = r#"{
+///     "type": "Feature",
+///     "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+///     "properties": { "name": "Dinagat Islands", "population": 127152 }
+/// }"#
+/// .parse()
+/// .unwrap();
+///
+/// assert_eq!(feature.property("name").unwrap().unwrap(), "Dinagat Islands");
+/// let population: u64 = feature.property_as("population").unwrap().unwrap();
+/// assert_eq!(population, 127152);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LazyFeature {
+    raw: Box<RawValue>,
+    geometry: Option<Range<usize>>,
+    properties: BTreeMap<String, Range<usize>>,
+}
+
+impl LazyFeature {
+    /// Build a LazyFeature from the JSON text of a Feature.
+    ///
+    /// This fails if `raw` isn't a JSON object with a `type` of `Feature` and a `geometry`, or if
+    /// its `properties` aren't an object or null.
+    pub fn from_raw(raw: Box<RawValue>) -> Result<Self> {
+        let text = raw.get();
+        let range = |value: &RawValue| {
+            let start = value.get().as_ptr() as usize - text.as_ptr() as usize;
+            start..start + value.get().len()
+        };
+
+        let borrowed: BorrowedFeature = serde_json::from_str(text)?;
+        let geometry = borrowed.geometry.map(range);
+        let properties = borrowed
+            .properties
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key.to_string(), range(value)))
+            .collect();
+        Ok(LazyFeature {
+            raw,
+            geometry,
+            properties,
+        })
+    }
+
+    /// The JSON text of the Feature, exactly as it was read.
+    pub fn as_raw(&self) -> &RawValue {
+        &self.raw
+    }
+
+    /// Parse the Feature's geometry, which is `None` if it's `null`.
+    pub fn geometry(&self) -> Result<Option<Geometry>> {
+        self.geometry
+            .clone()
+            .map(|range| Ok(serde_json::from_str(&self.raw.get()[range])?))
+            .transpose()
+    }
+
+    /// Parse the value of the property named `key`, if it exists.
+    pub fn property(&self, key: impl AsRef<str>) -> Result<Option<JsonValue>> {
+        self.property_as(key)
+    }
+
+    /// Deserialize the value of the property named `key` into `T`, if it exists.
+    pub fn property_as<T: DeserializeOwned>(&self, key: impl AsRef<str>) -> Result<Option<T>> {
+        self.properties
+            .get(key.as_ref())
+            .map(|range| Ok(serde_json::from_str(&self.raw.get()[range.clone()])?))
+            .transpose()
+    }
+
+    /// Return true iff the feature has a property named `key`.
+    pub fn contains_property(&self, key: &str) -> bool {
+        self.properties.contains_key(key)
+    }
+
+    /// The names of the Feature's properties, in sorted order.
+    pub fn property_keys(&self) -> impl Iterator<Item = &str> {
+        self.properties.keys().map(String::as_str)
+    }
+
+    /// Parse the whole Feature.
+    pub fn to_feature(&self) -> Result<Feature> {
+        let borrowed: BorrowedFeature = serde_json::from_str(self.raw.get())?;
+        borrowed.to_feature()
+    }
+}
+
+impl TryFrom<LazyFeature> for Feature {
+    type Error = Error;
+
+    fn try_from(lazy: LazyFeature) -> Result<Self> {
+        lazy.to_feature()
+    }
+}
+
+impl FromStr for LazyFeature {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_raw(serde_json::from_str(s)?)
+    }
+}
+
+impl Serialize for LazyFeature {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.raw.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for LazyFeature {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<LazyFeature, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error as SerdeError;
+
+        let raw = Box::<RawValue>::deserialize(deserializer)?;
+        LazyFeature::from_raw(raw).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Value;
+
+    fn feature_str() -> &'static str {
+        r#"{ "type": "Feature",
+            "geometry": { "type": "Point", "coordinates": [1.0, 2.0] },
+            "properties": { "name": "a\"b", "nested": { "c": [1, null] }, "n": 1.50 },
+            "id": 3 }"#
+    }
+
+    #[test]
+    fn parse_on_access() {
+        let lazy: LazyFeature = feature_str().parse().unwrap();
+        assert_eq!(lazy.property("name").unwrap().unwrap(), "a\"b");
+        assert_eq!(lazy.property_as::<f64>("n").unwrap(), Some(1.5));
+        assert!(lazy.property("missing").unwrap().is_none());
+        assert!(lazy.property_as::<String>("n").is_err());
+        assert_eq!(
+            lazy.property_keys().collect::<Vec<_>>(),
+            ["n", "name", "nested"]
+        );
+        assert_eq!(
+            lazy.geometry().unwrap().unwrap().value,
+            Value::Point(vec![1.0, 2.0])
+        );
+        assert_eq!(
+            lazy.to_feature().unwrap(),
+            feature_str().parse::<Feature>().unwrap()
+        );
+    }
+
+    #[test]
+    fn keeps_original_text() {
+        let lazy: LazyFeature = feature_str().parse().unwrap();
+        assert_eq!(lazy.as_raw().get(), feature_str());
+        assert_eq!(serde_json::to_string(&lazy).unwrap(), feature_str());
+    }
+
+    #[test]
+    fn invalid_parts_fail_on_access() {
+        let lazy: LazyFeature = r#"{"type": "Feature", "geometry": {"type": "Point"}}"#
+            .parse()
+            .unwrap();
+        assert!(lazy.geometry().is_err());
+        assert!(lazy.to_feature().is_err());
+
+        let no_geometry: LazyFeature = r#"{"type": "Feature", "geometry": null}"#.parse().unwrap();
+        assert!(no_geometry.geometry().unwrap().is_none());
+
+        assert!(r#"{"type": "Point", "coordinates": [1, 2]}"#.parse::<LazyFeature>().is_err());
+    }
+}
@@ -0,0 +1,287 @@
+//! Parse GeoJSON which deviates from the specification in common ways.
+//!
+//! Data in the wild often isn't quite valid GeoJSON. The functions in this module accept the most
+//! common deviations by normalizing the input before parsing it as usual, and return a
+//! [`Warning`] for each fix they made, so that the data can be accepted without hand-patching it
+//! first. They fix:
+//!
+//! - Features without a `properties` member, which is given a `null` value.
+//! - `type` names in the wrong case, like `"feature"` or `"POINT"`.
+//! - Coordinates given as numeric strings, like `"1.5"`.
+//! - `null` geometries inside a GeometryCollection, which are removed.
+//!
+//! Any other problems are errors, as with [`GeoJson::from_json_value`].
+//!
+//! # Examples
+//!
+//! ```
+//! use geojson::lenient::{self, Fix};
+//! use geojson::{GeoJson, Value};
+//!
+//! let (geojson, warnings) = lenient::from_str(
+//!     r#"{ "type": "feature", "geometry": { "type": "Point", "coordinates": ["1.5", 2] } }"#,
+//! )
+//! .unwrap();
+//!
+//! match geojson {
+//!     GeoJson::Feature(feature) => {
+//!         assert_eq!(feature.geometry.unwrap().value, Value::Point(vec![1.5, 2.0]))
+//!     }
+//!     _ => unreachable!(),
+//! }
+//! assert_eq!(warnings.len(), 3);
+//! assert_eq!(warnings[0].fix, Fix::TypeCase("feature".to_string()));
+//! assert_eq!(warnings[1].fix, Fix::MissingProperties);
+//! assert_eq!(warnings[2].pointer, "/geometry/coordinates/0");
+//! assert_eq!(warnings[2].fix, Fix::CoordinateString("1.5".to_string()));
+//! ```
+use crate::{GeoJson, JsonValue, Result};
+
+use std::fmt;
+use std::io::Read;
+
+/// A deviation from the specification which was fixed while parsing.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fix {
+    /// A Feature had no `properties` member.
+    MissingProperties,
+    /// A `type` was the name of a GeoJSON type in the wrong case.
+    TypeCase(String),
+    /// A coordinate was a string holding a number.
+    CoordinateString(String),
+    /// A GeometryCollection held a `null` geometry, which was removed.
+    NullGeometry,
+}
+
+impl fmt::Display for Fix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Fix::MissingProperties => f.write_str("added missing `properties`"),
+            Fix::TypeCase(type_) => write!(f, "corrected the case of type `{}`", type_),
+            Fix::CoordinateString(coordinate) => {
+                write!(f, "converted coordinate `{}` to a number", coordinate)
+            }
+            Fix::NullGeometry => f.write_str("removed a null geometry"),
+        }
+    }
+}
+
+/// A [`Fix`] made at a JSON pointer into the input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warning {
+    /// The JSON pointer to the member which was fixed, e.g. `/features/0/type`.
+    pub pointer: String,
+    pub fix: Fix,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at `{}`", self.fix, self.pointer)
+    }
+}
+
+/// Parse a `GeoJson` from a JSON value, fixing common deviations from the specification.
+pub fn from_json_value(mut value: JsonValue) -> Result<(GeoJson, Vec<Warning>)> {
+    let mut warnings = vec![];
+    normalize_object(&mut value, "", &mut warnings);
+    Ok((GeoJson::from_json_value(value)?, warnings))
+}
+
+/// Parse a `GeoJson` from a string, fixing common deviations from the specification.
+pub fn from_str(s: &str) -> Result<(GeoJson, Vec<Warning>)> {
+    from_json_value(serde_json::from_str(s)?)
+}
+
+/// Parse a `GeoJson` from a reader, fixing common deviations from the specification.
+pub fn from_reader<R: Read>(reader: R) -> Result<(GeoJson, Vec<Warning>)> {
+    from_json_value(serde_json::from_reader(reader)?)
+}
+
+const TYPES: [&str; 9] = [
+    "Point",
+    "MultiPoint",
+    "LineString",
+    "MultiLineString",
+    "Polygon",
+    "MultiPolygon",
+    "GeometryCollection",
+    "Feature",
+    "FeatureCollection",
+];
+
+/// Normalize the GeoJSON object `value`, if it is one. Anything else is left for the parser to
+/// reject.
+fn normalize_object(value: &mut JsonValue, pointer: &str, warnings: &mut Vec<Warning>) {
+    let object = match value {
+        JsonValue::Object(object) => object,
+        _ => return,
+    };
+
+    let mut type_ = match object.get("type").and_then(JsonValue::as_str) {
+        Some(type_) => type_.to_string(),
+        None => return,
+    };
+    if !TYPES.contains(&type_.as_str()) {
+        if let Some(fixed) = TYPES.iter().find(|t| t.eq_ignore_ascii_case(&type_)) {
+            warnings.push(Warning {
+                pointer: format!("{}/type", pointer),
+                fix: Fix::TypeCase(type_),
+            });
+            object.insert("type".to_string(), JsonValue::from(*fixed));
+            type_ = fixed.to_string();
+        }
+    }
+
+    match type_.as_str() {
+        "Feature" => {
+            if !object.contains_key("properties") {
+                warnings.push(Warning {
+                    pointer: format!("{}/properties", pointer),
+                    fix: Fix::MissingProperties,
+                });
+                object.insert("properties".to_string(), JsonValue::Null);
+            }
+            if let Some(geometry) = object.get_mut("geometry") {
+                normalize_object(geometry, &format!("{}/geometry", pointer), warnings);
+            }
+        }
+        "FeatureCollection" => {
+            if let Some(JsonValue::Array(features)) = object.get_mut("features") {
+                for (index, feature) in features.iter_mut().enumerate() {
+                    normalize_object(
+                        feature,
+                        &format!("{}/features/{}", pointer, index),
+                        warnings,
+                    );
+                }
+            }
+        }
+        "GeometryCollection" => {
+            if let Some(JsonValue::Array(geometries)) = object.get_mut("geometries") {
+                for (index, geometry) in geometries.iter_mut().enumerate() {
+                    let pointer = format!("{}/geometries/{}", pointer, index);
+                    if geometry.is_null() {
+                        warnings.push(Warning {
+                            pointer,
+                            fix: Fix::NullGeometry,
+                        });
+                    } else {
+                        normalize_object(geometry, &pointer, warnings);
+                    }
+                }
+                geometries.retain(|geometry| !geometry.is_null());
+            }
+        }
+        _ => {
+            if let Some(coordinates) = object.get_mut("coordinates") {
+                normalize_coordinates(coordinates, &format!("{}/coordinates", pointer), warnings);
+            }
+        }
+    }
+}
+
+/// Convert any numeric strings in (possibly nested) `coordinates` to numbers.
+fn normalize_coordinates(coordinates: &mut JsonValue, pointer: &str, warnings: &mut Vec<Warning>) {
+    match coordinates {
+        JsonValue::Array(elements) => {
+            for (index, element) in elements.iter_mut().enumerate() {
+                normalize_coordinates(element, &format!("{}/{}", pointer, index), warnings);
+            }
+        }
+        JsonValue::String(s) => {
+            let number = s
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64);
+            if let Some(number) = number {
+                warnings.push(Warning {
+                    pointer: pointer.to_string(),
+                    fix: Fix::CoordinateString(std::mem::take(s)),
+                });
+                *coordinates = JsonValue::Number(number);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, Value};
+    use serde_json::json;
+
+    #[test]
+    fn fixes() {
+        let (geojson, warnings) = from_json_value(json!({
+            "type": "featurecollection",
+            "features": [
+                { "type": "Feature", "geometry": null, "properties": {} },
+                {
+                    "type": "FEATURE",
+                    "geometry": {
+                        "type": "geometryCollection",
+                        "geometries": [
+                            null,
+                            { "type": "lineString", "coordinates": [[1, " 2 "], ["3e0", 4]] },
+                            null
+                        ]
+                    }
+                }
+            ]
+        }))
+        .unwrap();
+
+        let warnings: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            warnings,
+            [
+                "corrected the case of type `featurecollection` at `/type`",
+                "corrected the case of type `FEATURE` at `/features/1/type`",
+                "added missing `properties` at `/features/1/properties`",
+                "corrected the case of type `geometryCollection` at `/features/1/geometry/type`",
+                "removed a null geometry at `/features/1/geometry/geometries/0`",
+                "corrected the case of type `lineString` at `/features/1/geometry/geometries/1/type`",
+                "converted coordinate ` 2 ` to a number at `/features/1/geometry/geometries/1/coordinates/0/1`",
+                "converted coordinate `3e0` to a number at `/features/1/geometry/geometries/1/coordinates/1/0`",
+                "removed a null geometry at `/features/1/geometry/geometries/2`",
+            ]
+        );
+
+        let feature_collection = match geojson {
+            GeoJson::FeatureCollection(feature_collection) => feature_collection,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            feature_collection.features[1]
+                .geometry
+                .as_ref()
+                .unwrap()
+                .value,
+            Value::GeometryCollection(vec![Value::LineString(vec![
+                vec![1.0, 2.0],
+                vec![3.0, 4.0]
+            ])
+            .into()])
+        );
+    }
+
+    #[test]
+    fn valid_input_is_unchanged() {
+        let value = json!({
+            "type": "Feature",
+            "geometry": { "type": "Point", "coordinates": [1.5, 2] },
+            "properties": { "type": "point", "coordinates": ["1"] }
+        });
+        let (geojson, warnings) = from_json_value(value.clone()).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(geojson, GeoJson::from_json_value(value).unwrap());
+    }
+
+    #[test]
+    fn other_problems_are_errors() {
+        let err = from_str(r#"{ "type": "Point", "coordinates": ["one", 2] }"#).unwrap_err();
+        assert!(matches!(err, Error::ExpectedF64Value));
+    }
+}
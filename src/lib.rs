@@ -419,19 +419,35 @@
 /// Bounding Boxes
 ///
 /// [GeoJSON Format Specification § 5](https://tools.ietf.org/html/rfc7946#section-5)
+///
+/// Convert it to a [`BoundingBox`] to combine and compare them.
 pub type Bbox = Vec<f64>;
 
+mod bounding_box;
+pub use crate::bounding_box::BoundingBox;
+
 /// Positions
 ///
 /// [GeoJSON Format Specification § 3.1.1](https://tools.ietf.org/html/rfc7946#section-3.1.1)
+///
+/// Use [`PositionExt`] to read their values by name.
 pub type Position = Vec<f64>;
 
 pub type PointType = Position;
 pub type LineStringType = Vec<Position>;
 pub type PolygonType = Vec<Vec<Position>>;
 
+mod position;
+pub use crate::position::{Dimensions, IntoPosition, PositionExt};
+
 mod util;
 
+mod object_visitor;
+
+mod object_serializer;
+
+mod compact;
+
 mod geojson;
 pub use crate::geojson::GeoJson;
 
@@ -440,6 +456,12 @@ pub use crate::geometry::{Geometry, Value};
 
 pub mod feature;
 
+mod inline;
+pub use crate::inline::{InlineGeometry, InlinePosition, InlineValue};
+
+mod flat;
+pub use crate::flat::{FlatType, FlatValue};
+
 mod feature_collection;
 pub use crate::feature_collection::FeatureCollection;
 
@@ -449,7 +471,7 @@ mod feature_iterator;
 pub use crate::feature_iterator::FeatureIterator;
 
 pub mod errors;
-pub use crate::errors::{Error, Result};
+pub use crate::errors::{Error, Location, Result};
 
 #[cfg(feature = "geo-types")]
 mod conversion;
@@ -460,11 +482,54 @@ pub mod de;
 /// Write your struct to GeoJSON using [`serde`]
 pub mod ser;
 
+pub mod validate;
+
+pub mod lenient;
+
+mod parse_options;
+pub use parse_options::ParseOptions;
+
+mod write_options;
+pub use write_options::{WithOptions, WriteOptions};
+
 mod feature_reader;
-pub use feature_reader::FeatureReader;
+pub use feature_reader::{FeatureCollectionMembers, FeatureReader, Features};
 
 mod feature_writer;
-pub use feature_writer::FeatureWriter;
+pub use feature_writer::{FeatureWriter, TypePosition};
+
+mod feature_seq_reader;
+pub use feature_seq_reader::FeatureSeqReader;
+
+mod feature_seq_writer;
+pub use feature_seq_writer::FeatureSeqWriter;
+
+mod feature_lines_reader;
+pub use feature_lines_reader::FeatureLinesReader;
+
+mod feature_lines_writer;
+pub use feature_lines_writer::FeatureLinesWriter;
+
+mod borrowed_feature;
+pub use borrowed_feature::{BorrowedFeature, BorrowedObject};
+
+mod lazy_feature;
+pub use lazy_feature::LazyFeature;
+
+mod feature_splitter;
+
+mod feature_parser;
+pub use feature_parser::FeatureParser;
+
+#[cfg(feature = "tokio")]
+mod async_feature_reader;
+#[cfg(feature = "tokio")]
+pub use async_feature_reader::{AsyncFeatureReader, FeatureStream};
+
+#[cfg(feature = "tokio")]
+mod async_feature_writer;
+#[cfg(feature = "tokio")]
+pub use async_feature_writer::AsyncFeatureWriter;
 
 #[cfg(feature = "geo-types")]
 pub use conversion::quick_collection;
@@ -0,0 +1,287 @@
+//! Direct serialization of GeoJSON objects.
+//!
+//! The `Serialize` impls of [`Geometry`], [`Feature`], [`FeatureCollection`] and [`GeoJson`]
+//! write their members straight to the `Serializer`, rather than first converting to a
+//! [`JsonObject`], which would copy every position. The members are written in the same order as
+//! the `From<&T> for JsonObject` impls produce them, i.e. sorted by name, with any foreign member
+//! replacing a member of the same name.
+//!
+//! The members are written by [`WithOptions`], which rounds coordinates as its [`WriteOptions`]
+//! ask for.
+//!
+//! [`GeoJson`]: crate::GeoJson
+use crate::write_options::Rounded;
+use crate::{feature, Bbox, Feature, Geometry, InlineValue, JsonObject, Value};
+use crate::{WithOptions, WriteOptions};
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// The value of a member of a GeoJSON object.
+#[derive(Clone, Copy)]
+pub(crate) enum Member<'a> {
+    Type(&'static str),
+    Bbox(&'a Bbox),
+    /// The `coordinates` or `geometries` of a geometry.
+    Value(&'a Value),
+    InlineValue(&'a InlineValue),
+    Geometry(Option<&'a Geometry>),
+    /// Missing properties are written as an empty object.
+    Properties(Option<&'a JsonObject>),
+    Id(&'a feature::Id),
+    Features(&'a [Feature]),
+}
+
+impl Serialize for WithOptions<'_, Member<'_>> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let options = self.options;
+        match *self.value {
+            Member::Type(type_) => serializer.serialize_str(type_),
+            Member::Bbox(bbox) => match options.bbox_scale() {
+                Some(scale) => Rounded(bbox, scale).serialize(serializer),
+                None => bbox.serialize(serializer),
+            },
+            Member::Value(value) => WithOptions::new(value, options).serialize(serializer),
+            Member::InlineValue(value) => WithOptions::new(value, options).serialize(serializer),
+            Member::Geometry(Some(geometry)) => {
+                WithOptions::new(geometry, options).serialize(serializer)
+            }
+            Member::Geometry(None) => serializer.serialize_none(),
+            Member::Properties(Some(properties)) => properties.serialize(serializer),
+            Member::Properties(None) => serializer.serialize_map(Some(0))?.end(),
+            Member::Id(id) => id.serialize(serializer),
+            Member::Features(features) => serializer.collect_seq(
+                features
+                    .iter()
+                    .map(|feature| WithOptions::new(feature, options)),
+            ),
+        }
+    }
+}
+
+/// The `coordinates` or `geometries` of a geometry, with the coordinates rounded.
+impl Serialize for WithOptions<'_, Value> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let scale = match self.options.scale() {
+            Some(scale) => scale,
+            None => return self.value.serialize(serializer),
+        };
+        match self.value {
+            Value::Point(x) => Rounded(x, scale).serialize(serializer),
+            Value::MultiPoint(x) => Rounded(x, scale).serialize(serializer),
+            Value::LineString(x) => Rounded(x, scale).serialize(serializer),
+            Value::MultiLineString(x) => Rounded(x, scale).serialize(serializer),
+            Value::Polygon(x) => Rounded(x, scale).serialize(serializer),
+            Value::MultiPolygon(x) => Rounded(x, scale).serialize(serializer),
+            Value::GeometryCollection(x) => serializer.collect_seq(
+                x.iter()
+                    .map(|geometry| WithOptions::new(geometry, self.options)),
+            ),
+        }
+    }
+}
+
+/// Serialize an object made of `members`, which must be sorted by name, and `foreign_members`.
+pub(crate) fn serialize_object<'a, S, I>(
+    serializer: S,
+    members: I,
+    foreign_members: Option<&JsonObject>,
+    options: &WriteOptions,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    I: IntoIterator<Item = (&'static str, Member<'a>)>,
+    I::IntoIter: Clone,
+{
+    let no_foreign_members = JsonObject::new();
+    let foreign_members = foreign_members.unwrap_or(&no_foreign_members);
+    let members = members
+        .into_iter()
+        .filter(|(name, _)| !foreign_members.contains_key(*name));
+
+    // A `JsonObject` is only sorted by name without serde_json's `preserve_order` feature.
+    let mut foreign_members: Vec<_> = foreign_members.iter().collect();
+    foreign_members.sort_unstable_by_key(|(name, _)| *name);
+
+    let len = members.clone().count() + foreign_members.len();
+    let mut map = serializer.serialize_map(Some(len))?;
+    let mut members = members.peekable();
+    let mut foreign_members = foreign_members.into_iter().peekable();
+    loop {
+        let member_is_next = match (members.peek(), foreign_members.peek()) {
+            (Some((name, _)), Some((foreign_name, _))) => *name < foreign_name.as_str(),
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => break,
+        };
+        if member_is_next {
+            let (name, value) = members.next().expect("peeked");
+            map.serialize_entry(name, &WithOptions::new(&value, options))?;
+        } else {
+            let (name, value) = foreign_members.next().expect("peeked");
+            map.serialize_entry(name, value)?;
+        }
+    }
+    map.end()
+}
+
+/// The members of a [`Geometry`], sorted by name.
+pub(crate) fn geometry_members<'a>(
+    value: impl Into<Member<'a>>,
+    bbox: Option<&'a Bbox>,
+) -> impl Iterator<Item = (&'static str, Member<'a>)> + Clone {
+    let value = value.into();
+    let (value_name, type_name) = match value {
+        Member::Value(Value::GeometryCollection(_))
+        | Member::InlineValue(InlineValue::GeometryCollection(_)) => {
+            ("geometries", "GeometryCollection")
+        }
+        Member::Value(value) => ("coordinates", value.type_name()),
+        Member::InlineValue(value) => ("coordinates", value.type_name()),
+        _ => unreachable!("not the value of a geometry"),
+    };
+    bbox.map(|bbox| ("bbox", Member::Bbox(bbox)))
+        .into_iter()
+        .chain([(value_name, value), ("type", Member::Type(type_name))])
+}
+
+impl<'a> From<&'a Value> for Member<'a> {
+    fn from(value: &'a Value) -> Self {
+        Member::Value(value)
+    }
+}
+
+impl<'a> From<&'a InlineValue> for Member<'a> {
+    fn from(value: &'a InlineValue) -> Self {
+        Member::InlineValue(value)
+    }
+}
+
+/// The `coordinates` or `geometries` of an inline geometry, with the coordinates rounded.
+impl Serialize for WithOptions<'_, InlineValue> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let scale = match self.options.scale() {
+            Some(scale) => scale,
+            None => return self.value.serialize(serializer),
+        };
+        match self.value {
+            InlineValue::Point(x) => Rounded(x, scale).serialize(serializer),
+            InlineValue::MultiPoint(x) => Rounded(x, scale).serialize(serializer),
+            InlineValue::LineString(x) => Rounded(x, scale).serialize(serializer),
+            InlineValue::MultiLineString(x) => Rounded(x, scale).serialize(serializer),
+            InlineValue::Polygon(x) => Rounded(x, scale).serialize(serializer),
+            InlineValue::MultiPolygon(x) => Rounded(x, scale).serialize(serializer),
+            InlineValue::GeometryCollection(x) => serializer.collect_seq(
+                x.iter()
+                    .map(|geometry| WithOptions::new(geometry, self.options)),
+            ),
+        }
+    }
+}
+
+/// A [`Value`] serialized as a geometry object without a bbox or foreign members, as it's
+/// displayed.
+pub(crate) struct ValueObject<'a>(pub(crate) &'a Value);
+
+impl Serialize for ValueObject<'_> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_object(
+            serializer,
+            geometry_members(self.0, None),
+            None,
+            &WriteOptions::default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Feature, FeatureCollection, GeoJson, Geometry, JsonObject, JsonValue, Value};
+    use serde_json::json;
+
+    /// Check that serializing directly gives the same output as going through a `JsonObject`.
+    fn check(geojson: GeoJson) {
+        let expected = serde_json::to_string(&JsonObject::from(&geojson)).unwrap();
+        assert_eq!(serde_json::to_string(&geojson).unwrap(), expected);
+        assert_eq!(geojson.to_string(), expected);
+
+        let expected_pretty = serde_json::to_string_pretty(&JsonObject::from(&geojson)).unwrap();
+        assert_eq!(
+            serde_json::to_string_pretty(&geojson).unwrap(),
+            expected_pretty
+        );
+    }
+
+    fn foreign_members(value: JsonValue) -> Option<JsonObject> {
+        match value {
+            JsonValue::Object(object) => Some(object),
+            _ => unreachable!(),
+        }
+    }
+
+    fn geometry() -> Geometry {
+        Geometry {
+            bbox: Some(vec![1.0, 2.0, 3.0, 4.0]),
+            value: Value::GeometryCollection(vec![
+                Geometry::new(Value::Point(vec![1.0, 2.0])),
+                Geometry::new(Value::Polygon(vec![vec![
+                    vec![1.0, 2.0],
+                    vec![3.0, 2.0],
+                    vec![3.0, 4.0],
+                    vec![1.0, 2.0],
+                ]])),
+            ]),
+            foreign_members: foreign_members(json!({ "a": 1, "crs": null, "z": [1] })),
+        }
+    }
+
+    fn feature() -> Feature {
+        Feature {
+            bbox: Some(vec![1.0, 2.0, 3.0, 4.0]),
+            geometry: Some(geometry()),
+            id: Some(crate::feature::Id::String("1".to_string())),
+            properties: foreign_members(json!({ "name": "a", "nested": { "b": [1.5] } })),
+            foreign_members: foreign_members(json!({ "a": 1, "hi": "there", "zz": null })),
+        }
+    }
+
+    #[test]
+    fn same_as_json_object() {
+        check(GeoJson::from(Value::MultiPoint(vec![vec![1.0, 2.5, 3.0]])));
+        check(GeoJson::from(geometry()));
+        check(GeoJson::from(feature()));
+        check(GeoJson::from(Feature::default()));
+        check(GeoJson::from(FeatureCollection {
+            bbox: None,
+            features: vec![feature(), Feature::from(Value::Point(vec![0.0, 0.0]))],
+            foreign_members: foreign_members(json!({ "name": "collection" })),
+        }));
+    }
+
+    #[test]
+    fn foreign_members_replace_members() {
+        let mut feature = feature();
+        feature.foreign_members = foreign_members(json!({ "id": 2, "type": "Other", "a": 1 }));
+        check(GeoJson::from(feature));
+    }
+
+    #[test]
+    fn value_display() {
+        let value = geometry().value;
+        assert_eq!(
+            value.to_string(),
+            serde_json::to_string(&JsonObject::from(&value)).unwrap()
+        );
+    }
+}
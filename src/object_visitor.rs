@@ -0,0 +1,1013 @@
+//! Streaming deserialization of GeoJSON objects.
+//!
+//! The `Deserialize` impls of [`Geometry`], [`Feature`], [`FeatureCollection`] and [`GeoJson`]
+//! read the members of an object straight into a [`GeoJsonObject`], building positions as they
+//! go, rather than first building a [`JsonObject`] and then converting it with the `util::get_*`
+//! functions. Since the `type` member may come after the members it determines the meaning of,
+//! every member which means something for *some* GeoJSON object is kept until the object has
+//! ended, and then either converted or moved into the foreign members.
+//!
+//! Members like `coordinates` and `features` are always read straight into their GeoJSON form,
+//! whether or not the `type` has been read yet, since it often comes last. That form keeps
+//! enough of what was read, e.g. whether each number was an integer, to give back the same JSON
+//! if they turn out to be foreign members.
+//!
+//! The resulting values and errors are the same as those of the `TryFrom<JsonObject>` impls.
+use crate::errors::{Error, Result};
+use crate::flat::{FlatBuilder, FlatType, FlatValue};
+use crate::util::{expect_owned_array, json_to_bbox};
+use crate::{feature, Feature, FeatureCollection, GeoJson, Geometry, Value};
+use crate::{InlineGeometry, InlinePosition, InlineValue};
+use crate::{JsonObject, JsonValue};
+
+use serde_json::Number as JsonNumber;
+
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Upper bound for preallocating arrays from a size hint, so that a bogus hint can't exhaust
+/// memory.
+const MAX_PREALLOCATED: usize = 4096;
+
+/// The members of a GeoJSON object of any type.
+#[derive(Default)]
+pub(crate) struct GeoJsonObject {
+    type_: Option<JsonValue>,
+    bbox: Option<JsonValue>,
+    coordinates: Option<Coordinates>,
+    geometries: Option<ArrayOr<ObjectOr<GeoJsonObject>>>,
+    geometry: Option<ObjectOr<Box<GeoJsonObject>>>,
+    properties: Option<JsonValue>,
+    id: Option<JsonValue>,
+    features: Option<ArrayOr<ObjectOr<GeoJsonObject>>>,
+    foreign_members: JsonObject,
+}
+
+impl GeoJsonObject {
+    /// Mirrors `GeoJson::try_from(JsonObject)`.
+    pub(crate) fn into_geojson(self) -> Result<GeoJson> {
+        match &self.type_ {
+            Some(JsonValue::String(type_)) => match type_.as_str() {
+                "Feature" => self.into_feature().map(GeoJson::Feature),
+                "FeatureCollection" => self
+                    .into_feature_collection()
+                    .map(GeoJson::FeatureCollection),
+                "Point" | "MultiPoint" | "LineString" | "MultiLineString" | "Polygon"
+                | "MultiPolygon" | "GeometryCollection" => {
+                    self.into_geometry().map(GeoJson::Geometry)
+                }
+                _ => Err(Error::EmptyType),
+            },
+            _ => Err(Error::GeometryUnknownType("type".to_owned())),
+        }
+    }
+
+    /// Mirrors `Geometry::try_from(JsonObject)`.
+    pub(crate) fn into_geometry(mut self) -> Result<Geometry> {
+        let bbox = self.take_bbox()?;
+        let value = self.take_value()?;
+        Ok(Geometry {
+            bbox,
+            value,
+            foreign_members: self.into_foreign_members(),
+        })
+    }
+
+    /// Like `into_geometry`, but with inline positions.
+    pub(crate) fn into_inline_geometry(mut self) -> Result<InlineGeometry> {
+        let bbox = self.take_bbox()?;
+        let value = self.take_inline_value()?;
+        Ok(InlineGeometry {
+            bbox,
+            value,
+            foreign_members: self.into_foreign_members(),
+        })
+    }
+
+    /// Mirrors `Feature::try_from(JsonObject)`.
+    pub(crate) fn into_feature(mut self) -> Result<Feature> {
+        let type_ = self.take_type()?;
+        if type_ != "Feature" {
+            return Err(Error::NotAFeature(type_));
+        }
+        let geometry = match self.geometry.take() {
+            Some(ObjectOr::Object(geometry)) => Some(
+                geometry
+                    .into_geometry()
+                    .map_err(|e| e.in_member("geometry"))?,
+            ),
+            Some(ObjectOr::Other(JsonValue::Null)) => None,
+            Some(ObjectOr::Other(value)) => {
+                return Err(Error::FeatureInvalidGeometryValue(value).in_member("geometry"))
+            }
+            None => return Err(Error::ExpectedProperty("geometry".to_string())),
+        };
+        let properties = match self.properties.take() {
+            Some(JsonValue::Object(properties)) => Some(properties),
+            Some(JsonValue::Null) | None => None,
+            Some(value) => {
+                return Err(Error::PropertiesExpectedObjectOrNull(value).in_member("properties"))
+            }
+        };
+        let id = match self.id.take() {
+            Some(JsonValue::Number(x)) => Some(feature::Id::Number(x)),
+            Some(JsonValue::String(s)) => Some(feature::Id::String(s)),
+            Some(value) => return Err(Error::FeatureInvalidIdentifierType(value).in_member("id")),
+            None => None,
+        };
+        let bbox = self.take_bbox()?;
+        Ok(Feature {
+            bbox,
+            geometry,
+            id,
+            properties,
+            foreign_members: self.into_foreign_members(),
+        })
+    }
+
+    /// Mirrors `FeatureCollection::try_from(JsonObject)`.
+    pub(crate) fn into_feature_collection(mut self) -> Result<FeatureCollection> {
+        let type_ = self.take_type()?;
+        if type_ != "FeatureCollection" {
+            return Err(Error::ExpectedType {
+                expected: "FeatureCollection".to_owned(),
+                actual: type_,
+            });
+        }
+        let bbox = self.take_bbox()?;
+        let features = match self.features.take() {
+            Some(features) => features
+                .into_objects(GeoJsonObject::into_feature)
+                .map_err(|e| e.in_member("features"))?,
+            None => return Err(Error::ExpectedProperty("features".to_string())),
+        };
+        Ok(FeatureCollection {
+            bbox,
+            features,
+            foreign_members: self.into_foreign_members(),
+        })
+    }
+
+    /// Mirrors `util::get_value`.
+    fn take_value(&mut self) -> Result<Value> {
+        let type_ = self.take_type()?;
+        let value = match type_.as_str() {
+            "Point" => Value::Point(self.take_coordinates(Coordinates::into_position)?),
+            "MultiPoint" => Value::MultiPoint(self.take_coordinates(Coordinates::into_positions)?),
+            "LineString" => Value::LineString(self.take_coordinates(Coordinates::into_positions)?),
+            "MultiLineString" => {
+                Value::MultiLineString(self.take_coordinates(Coordinates::into_positions_2d)?)
+            }
+            "Polygon" => Value::Polygon(self.take_coordinates(Coordinates::into_positions_2d)?),
+            "MultiPolygon" => {
+                Value::MultiPolygon(self.take_coordinates(Coordinates::into_positions_3d)?)
+            }
+            "GeometryCollection" => match self.geometries.take() {
+                Some(geometries) => Value::GeometryCollection(
+                    geometries
+                        .into_objects(GeoJsonObject::into_geometry)
+                        .map_err(|e| e.in_member("geometries"))?,
+                ),
+                None => return Err(Error::ExpectedProperty("geometries".to_string())),
+            },
+            _ => return Err(Error::GeometryUnknownType(type_)),
+        };
+        Ok(value)
+    }
+
+    /// Like `take_value`, but with inline positions.
+    fn take_inline_value(&mut self) -> Result<InlineValue> {
+        let type_ = self.take_type()?;
+        let value = match type_.as_str() {
+            "Point" => InlineValue::Point(self.take_coordinates(Coordinates::into_position)?),
+            "MultiPoint" => {
+                InlineValue::MultiPoint(self.take_coordinates(Coordinates::into_positions)?)
+            }
+            "LineString" => {
+                InlineValue::LineString(self.take_coordinates(Coordinates::into_positions)?)
+            }
+            "MultiLineString" => {
+                InlineValue::MultiLineString(self.take_coordinates(Coordinates::into_positions_2d)?)
+            }
+            "Polygon" => {
+                InlineValue::Polygon(self.take_coordinates(Coordinates::into_positions_2d)?)
+            }
+            "MultiPolygon" => {
+                InlineValue::MultiPolygon(self.take_coordinates(Coordinates::into_positions_3d)?)
+            }
+            "GeometryCollection" => match self.geometries.take() {
+                Some(geometries) => InlineValue::GeometryCollection(
+                    geometries
+                        .into_objects(GeoJsonObject::into_inline_geometry)
+                        .map_err(|e| e.in_member("geometries"))?,
+                ),
+                None => return Err(Error::ExpectedProperty("geometries".to_string())),
+            },
+            _ => return Err(Error::GeometryUnknownType(type_)),
+        };
+        Ok(value)
+    }
+
+    /// Like `take_value`, but into a single buffer. Any other members are ignored.
+    pub(crate) fn into_flat_value(mut self) -> Result<FlatValue> {
+        let flat_type = FlatType::from_type(self.take_type()?)?;
+        let coordinates = self
+            .coordinates
+            .take()
+            .ok_or_else(|| Error::ExpectedProperty("coordinates".to_string()))?;
+        let mut builder = FlatBuilder::new(flat_type);
+        coordinates
+            .flatten_into(flat_type.depth(), &mut builder)
+            .map_err(|e| e.in_member("coordinates"))?;
+        builder.finish()
+    }
+
+    fn take_type(&mut self) -> Result<String> {
+        match self.type_.take() {
+            Some(JsonValue::String(type_)) => Ok(type_),
+            Some(value) => Err(Error::ExpectedStringValue(value)),
+            None => Err(Error::ExpectedProperty("type".to_string())),
+        }
+    }
+
+    fn take_bbox(&mut self) -> Result<Option<crate::Bbox>> {
+        self.bbox
+            .take()
+            .map(json_to_bbox)
+            .transpose()
+            .map_err(|e| e.in_member("bbox"))
+    }
+
+    fn take_coordinates<T>(&mut self, convert: fn(Coordinates) -> Result<T>) -> Result<T> {
+        let coordinates = self
+            .coordinates
+            .take()
+            .ok_or_else(|| Error::ExpectedProperty("coordinates".to_string()))?;
+        convert(coordinates).map_err(|e| e.in_member("coordinates"))
+    }
+
+    /// Every member which hasn't been taken by now is a foreign member.
+    fn into_foreign_members(self) -> Option<JsonObject> {
+        let mut foreign_members = self.foreign_members;
+        let mut insert = |key: &str, value: Option<JsonValue>| {
+            if let Some(value) = value {
+                foreign_members.insert(key.to_string(), value);
+            }
+        };
+        insert("type", self.type_);
+        insert("bbox", self.bbox);
+        insert("coordinates", self.coordinates.map(Coordinates::into_json));
+        insert("geometries", self.geometries.map(ArrayOr::into_json));
+        insert("geometry", self.geometry.map(ObjectOr::into_json));
+        insert("properties", self.properties);
+        insert("id", self.id);
+        insert("features", self.features.map(ArrayOr::into_json));
+        if foreign_members.is_empty() {
+            None
+        } else {
+            Some(foreign_members)
+        }
+    }
+
+    /// Turn the object back into JSON, when it turns out to be a foreign member.
+    fn into_json(self) -> JsonValue {
+        JsonValue::Object(self.into_foreign_members().unwrap_or_default())
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoJsonObject {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(GeoJsonObjectVisitor)
+    }
+}
+
+struct GeoJsonObjectVisitor;
+
+impl<'de> Visitor<'de> for GeoJsonObjectVisitor {
+    type Value = GeoJsonObject;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a GeoJSON object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        // Like building a JsonObject, a repeated member replaces the earlier one.
+        let mut object = GeoJsonObject::default();
+        while let Some(key) = map.next_key::<Member>()? {
+            match key {
+                Member::Type => object.type_ = Some(map.next_value()?),
+                Member::Bbox => object.bbox = Some(map.next_value()?),
+                Member::Coordinates => object.coordinates = Some(map.next_value()?),
+                Member::Geometries => object.geometries = Some(map.next_value()?),
+                Member::Geometry => object.geometry = Some(map.next_value()?),
+                Member::Properties => object.properties = Some(map.next_value()?),
+                Member::Id => object.id = Some(map.next_value()?),
+                Member::Features => object.features = Some(map.next_value()?),
+                Member::Other(key) => {
+                    let value = map.next_value()?;
+                    object.foreign_members.insert(key, value);
+                }
+            }
+        }
+        Ok(object)
+    }
+}
+
+/// The name of a member of a GeoJSON object.
+enum Member {
+    Type,
+    Bbox,
+    Coordinates,
+    Geometries,
+    Geometry,
+    Properties,
+    Id,
+    Features,
+    Other(String),
+}
+
+impl Member {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "type" => Member::Type,
+            "bbox" => Member::Bbox,
+            "coordinates" => Member::Coordinates,
+            "geometries" => Member::Geometries,
+            "geometry" => Member::Geometry,
+            "properties" => Member::Properties,
+            "id" => Member::Id,
+            "features" => Member::Features,
+            _ => return None,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Member {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MemberVisitor;
+
+        impl<'de> Visitor<'de> for MemberVisitor {
+            type Value = Member;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a member name")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Member, E> {
+                Ok(Member::from_name(v).unwrap_or_else(|| Member::Other(v.to_string())))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> std::result::Result<Member, E> {
+                Ok(Member::from_name(&v).unwrap_or(Member::Other(v)))
+            }
+        }
+
+        deserializer.deserialize_identifier(MemberVisitor)
+    }
+}
+
+/// Implements the `Visitor` methods for every kind of value other than maps and sequences, by
+/// keeping the value as JSON in the given variant.
+macro_rules! visit_other_as_json {
+    ($other:path) => {
+        fn visit_bool<E: de::Error>(self, v: bool) -> std::result::Result<Self::Value, E> {
+            Ok($other(JsonValue::Bool(v)))
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> std::result::Result<Self::Value, E> {
+            Ok($other(JsonValue::from(v)))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> std::result::Result<Self::Value, E> {
+            Ok($other(JsonValue::from(v)))
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> std::result::Result<Self::Value, E> {
+            Ok($other(JsonValue::from(v)))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Self::Value, E> {
+            Ok($other(JsonValue::String(v.to_string())))
+        }
+
+        fn visit_string<E: de::Error>(self, v: String) -> std::result::Result<Self::Value, E> {
+            Ok($other(JsonValue::String(v)))
+        }
+
+        fn visit_unit<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+            Ok($other(JsonValue::Null))
+        }
+
+        fn visit_none<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+            Ok($other(JsonValue::Null))
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> std::result::Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(self)
+        }
+    };
+}
+
+/// A value which is expected to be an object, deserialized as `T`.
+pub(crate) enum ObjectOr<T> {
+    Object(T),
+    Other(JsonValue),
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ObjectOr<T> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ObjectOrVisitor(PhantomData))
+    }
+}
+
+struct ObjectOrVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ObjectOrVisitor<T> {
+    type Value = ObjectOr<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_map<A>(self, map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        T::deserialize(MapAccessDeserializer::new(map)).map(ObjectOr::Object)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        JsonValue::deserialize(SeqAccessDeserializer::new(seq)).map(ObjectOr::Other)
+    }
+
+    visit_other_as_json!(ObjectOr::Other);
+}
+
+impl ObjectOr<Box<GeoJsonObject>> {
+    fn into_json(self) -> JsonValue {
+        match self {
+            ObjectOr::Object(object) => object.into_json(),
+            ObjectOr::Other(value) => value,
+        }
+    }
+}
+
+/// A value which is expected to be an array of `T`.
+pub(crate) enum ArrayOr<T> {
+    Array(Vec<T>),
+    Other(JsonValue),
+}
+
+impl ArrayOr<ObjectOr<GeoJsonObject>> {
+    /// Mirrors `util::get_features` and `util::get_geometries`.
+    fn into_objects<T>(self, convert: fn(GeoJsonObject) -> Result<T>) -> Result<Vec<T>> {
+        match self {
+            ArrayOr::Array(items) => items
+                .into_iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    match item {
+                        ObjectOr::Object(object) => convert(object),
+                        ObjectOr::Other(value) => Err(Error::ExpectedObjectValue(value)),
+                    }
+                    .map_err(|e| e.in_element(index))
+                })
+                .collect(),
+            ArrayOr::Other(value) => Err(expect_owned_array(value).unwrap_err()),
+        }
+    }
+
+    fn into_json(self) -> JsonValue {
+        match self {
+            ArrayOr::Array(items) => JsonValue::Array(
+                items
+                    .into_iter()
+                    .map(|item| match item {
+                        ObjectOr::Object(object) => object.into_json(),
+                        ObjectOr::Other(value) => value,
+                    })
+                    .collect(),
+            ),
+            ArrayOr::Other(value) => value,
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ArrayOr<T> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ArrayOrVisitor(PhantomData))
+    }
+}
+
+struct ArrayOrVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ArrayOrVisitor<T> {
+    type Value = ArrayOr<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let capacity = seq.size_hint().unwrap_or(0).min(MAX_PREALLOCATED);
+        let mut items = Vec::with_capacity(capacity);
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(ArrayOr::Array(items))
+    }
+
+    fn visit_map<A>(self, map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        JsonObject::deserialize(MapAccessDeserializer::new(map))
+            .map(|object| ArrayOr::Other(JsonValue::Object(object)))
+    }
+
+    visit_other_as_json!(ArrayOr::Other);
+}
+
+/// The value of a `coordinates` member, whose nesting depth isn't known until the `type` member
+/// has been read.
+///
+/// Arrays of numbers are read straight into an [`InlinePosition`], so they needn't be converted
+/// later, and only need an allocation of their own if they end up as a [`Position`].
+///
+/// [`Position`]: crate::Position
+pub(crate) enum Coordinates {
+    Number(JsonNumber),
+    /// A non-empty array holding only numbers, with a bit set in the `u64` for each number which
+    /// was an integer, so that the array can be turned back into the same JSON.
+    Position(InlinePosition, u64),
+    /// Any other array, including arrays of numbers which can't be kept as a position, e.g.
+    /// integers too large for an `f64`.
+    Array(Vec<Coordinates>),
+    Other(JsonValue),
+}
+
+impl Coordinates {
+    /// Mirrors `util::json_to_position`.
+    fn into_position<P: From<InlinePosition>>(self) -> Result<P> {
+        match self {
+            Coordinates::Position(position, _) if position.len() >= 2 => Ok(P::from(position)),
+            Coordinates::Position(position, _) => Err(Error::PositionTooShort(position.len())),
+            Coordinates::Array(items) if items.len() < 2 => {
+                Err(Error::PositionTooShort(items.len()))
+            }
+            Coordinates::Array(items) => items
+                .into_iter()
+                .enumerate()
+                .map(|(index, item)| match item {
+                    Coordinates::Number(number) => number
+                        .as_f64()
+                        .ok_or_else(|| Error::ExpectedF64Value.in_element(index)),
+                    _ => Err(Error::ExpectedF64Value.in_element(index)),
+                })
+                .collect::<Result<InlinePosition>>()
+                .map(P::from),
+            Coordinates::Number(_) | Coordinates::Other(_) => {
+                Err(Error::ExpectedArrayValue("None".to_string()))
+            }
+        }
+    }
+
+    /// Mirrors `util::json_to_1d_positions`.
+    fn into_positions<P: From<InlinePosition>>(self) -> Result<Vec<P>> {
+        self.into_items()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| item.into_position().map_err(|e| e.in_element(index)))
+            .collect()
+    }
+
+    /// Mirrors `util::json_to_2d_positions`.
+    fn into_positions_2d<P: From<InlinePosition>>(self) -> Result<Vec<Vec<P>>> {
+        self.into_items()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| item.into_positions().map_err(|e| e.in_element(index)))
+            .collect()
+    }
+
+    /// Mirrors `util::json_to_3d_positions`.
+    fn into_positions_3d<P: From<InlinePosition>>(self) -> Result<Vec<Vec<Vec<P>>>> {
+        self.into_items()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| item.into_positions_2d().map_err(|e| e.in_element(index)))
+            .collect()
+    }
+
+    /// Append the positions nested `levels` arrays deep to `builder`.
+    fn flatten_into(self, levels: usize, builder: &mut FlatBuilder) -> Result<()> {
+        if levels == 0 {
+            let position: InlinePosition = self.into_position()?;
+            return builder.push_position(&position);
+        }
+        for (index, item) in self.into_items()?.into_iter().enumerate() {
+            item.flatten_into(levels - 1, builder)
+                .map_err(|e| e.in_element(index))?;
+        }
+        builder.end_array(levels);
+        Ok(())
+    }
+
+    /// Mirrors `util::expect_array`.
+    fn into_items(self) -> Result<Vec<Coordinates>> {
+        match self {
+            Coordinates::Array(items) => Ok(items),
+            Coordinates::Position(position, integers) => Ok(numbers(&position, integers)
+                .map(Coordinates::Number)
+                .collect()),
+            Coordinates::Number(_) | Coordinates::Other(_) => {
+                Err(Error::ExpectedArrayValue("None".to_string()))
+            }
+        }
+    }
+
+    fn into_json(self) -> JsonValue {
+        match self {
+            Coordinates::Number(number) => JsonValue::Number(number),
+            Coordinates::Position(position, integers) => JsonValue::Array(
+                numbers(&position, integers)
+                    .map(JsonValue::Number)
+                    .collect(),
+            ),
+            Coordinates::Array(items) => {
+                JsonValue::Array(items.into_iter().map(Coordinates::into_json).collect())
+            }
+            Coordinates::Other(value) => value,
+        }
+    }
+}
+
+/// The numbers of a [`Coordinates::Position`], as they were read.
+fn numbers(position: &InlinePosition, integers: u64) -> impl Iterator<Item = JsonNumber> + '_ {
+    // Positions only hold finite numbers, so none are skipped.
+    position
+        .iter()
+        .enumerate()
+        .filter_map(move |(index, &value)| {
+            if index < 64 && integers >> index & 1 == 1 {
+                Some(integer_number(value))
+            } else {
+                JsonNumber::from_f64(value)
+            }
+        })
+}
+
+/// The integer which `value` was read from, as `JsonValue` would have kept it.
+fn integer_number(value: f64) -> JsonNumber {
+    if value < 0.0 {
+        JsonNumber::from(value as i64)
+    } else {
+        JsonNumber::from(value as u64)
+    }
+}
+
+impl<'de> Deserialize<'de> for Coordinates {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(CoordinatesVisitor)
+    }
+}
+
+struct CoordinatesVisitor;
+
+impl<'de> Visitor<'de> for CoordinatesVisitor {
+    type Value = Coordinates;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> std::result::Result<Self::Value, E> {
+        Ok(Coordinates::Number(v.into()))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> std::result::Result<Self::Value, E> {
+        Ok(Coordinates::Number(v.into()))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> std::result::Result<Self::Value, E> {
+        // Like `JsonValue`, which has no numbers which aren't finite.
+        Ok(match JsonNumber::from_f64(v) {
+            Some(number) => Coordinates::Number(number),
+            None => Coordinates::Other(JsonValue::Null),
+        })
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> std::result::Result<Self::Value, E> {
+        Ok(Coordinates::Other(JsonValue::Bool(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Self::Value, E> {
+        Ok(Coordinates::Other(JsonValue::String(v.to_string())))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+        Ok(Coordinates::Other(JsonValue::Null))
+    }
+
+    fn visit_none<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+        Ok(Coordinates::Other(JsonValue::Null))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> std::result::Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_map<A>(self, map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        JsonObject::deserialize(MapAccessDeserializer::new(map))
+            .map(|object| Coordinates::Other(JsonValue::Object(object)))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let capacity = seq.size_hint().unwrap_or(0).min(MAX_PREALLOCATED);
+        // Numbers are collected into a position until anything else shows up.
+        let mut position = InlinePosition::new();
+        let mut integers = 0;
+        let mut items: Option<Vec<Coordinates>> = None;
+        while let Some(item) = seq.next_element()? {
+            if let Some(items) = &mut items {
+                items.push(item);
+                continue;
+            }
+            if let Coordinates::Number(number) = &item {
+                if let Some(value) = number.as_f64() {
+                    let index = position.len();
+                    if number.is_f64() {
+                        position.push(value);
+                        continue;
+                    }
+                    if index < 64 && integer_number(value) == *number {
+                        integers |= 1 << index;
+                        position.push(value);
+                        continue;
+                    }
+                }
+            }
+            let mut new_items = Vec::with_capacity(capacity.max(position.len() + 1));
+            new_items.extend(numbers(&position, integers).map(Coordinates::Number));
+            new_items.push(item);
+            items = Some(new_items);
+        }
+        Ok(match items {
+            Some(items) => Coordinates::Array(items),
+            None if position.is_empty() => Coordinates::Array(vec![]),
+            None => Coordinates::Position(position, integers),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::convert::TryFrom;
+
+    /// Deserialize `value` both through the visitors and through the `TryFrom<JsonObject>` impls,
+    /// and check that the results agree.
+    fn check(value: JsonValue) -> std::result::Result<GeoJson, String> {
+        let streamed = GeoJson::deserialize(&value).map_err(|err| err.to_string());
+        let converted = GeoJson::from_json_value(value).map_err(|err| err.to_string());
+        assert_eq!(streamed, converted);
+        streamed
+    }
+
+    #[test]
+    fn type_after_coordinates() {
+        let geojson = check(json!({
+            "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 0]]],
+            "bbox": [0, 0, 1, 1],
+            "type": "Polygon",
+            "name": "square"
+        }))
+        .unwrap();
+        let geometry = Geometry::try_from(geojson).unwrap();
+        assert_eq!(
+            geometry.value,
+            Value::Polygon(vec![vec![
+                vec![0.0, 0.0],
+                vec![1.0, 0.0],
+                vec![1.0, 1.0],
+                vec![0.0, 0.0]
+            ]])
+        );
+        assert_eq!(geometry.bbox, Some(vec![0.0, 0.0, 1.0, 1.0]));
+        assert_eq!(
+            geometry.foreign_members.unwrap().get("name").unwrap(),
+            "square"
+        );
+    }
+
+    #[test]
+    fn nested_objects() {
+        check(json!({
+            "features": [
+                {
+                    "properties": { "name": "a" },
+                    "geometry": {
+                        "geometries": [
+                            { "coordinates": [1, 2], "type": "Point" },
+                            { "coordinates": [[1, 2], [3, 4]], "type": "LineString" },
+                            { "coordinates": [], "type": "MultiPolygon" }
+                        ],
+                        "type": "GeometryCollection"
+                    },
+                    "id": 7,
+                    "type": "Feature"
+                },
+                { "geometry": null, "properties": null, "id": "b", "type": "Feature" }
+            ],
+            "type": "FeatureCollection"
+        }))
+        .unwrap();
+    }
+
+    #[test]
+    fn members_of_other_types_are_foreign_members() {
+        let geojson = check(json!({
+            "type": "Point",
+            "coordinates": [1, 2],
+            "geometries": [],
+            "properties": { "a": 1 },
+            "features": {}
+        }))
+        .unwrap();
+        let foreign_members = Geometry::try_from(geojson)
+            .unwrap()
+            .foreign_members
+            .unwrap();
+        assert_eq!(foreign_members.len(), 3);
+    }
+
+    #[test]
+    fn foreign_members_are_unchanged() {
+        let inputs = [
+            (
+                r#"{"type":"Feature","geometry":null,"properties":null,"coordinates":[1,2]}"#,
+                "coordinates",
+            ),
+            (
+                r#"{"coordinates":[1,2],"type":"Feature","geometry":null,"properties":null}"#,
+                "coordinates",
+            ),
+            (
+                r#"{"type":"FeatureCollection","features":[],"geometry":{"type":"Point","coordinates":[1,2]}}"#,
+                "geometry",
+            ),
+            (
+                r#"{"type":"Point","coordinates":[1,2],"geometry":{"foo":1}}"#,
+                "geometry",
+            ),
+            (
+                r#"{"type":"Point","coordinates":[1,2],"features":[{"id":1}]}"#,
+                "features",
+            ),
+            (
+                r#"{"type":"Point","coordinates":[1,2],"geometries":[{"type":"Point","coordinates":[3,4]}]}"#,
+                "geometries",
+            ),
+            (
+                r#"{"coordinates":[1,-2,3.5,-0,2.0,18446744073709551615,-9007199254740993],"geometry":null,"properties":null,"type":"Feature"}"#,
+                "coordinates",
+            ),
+            (
+                r#"{"coordinates":[[1,2],[3,"4"],{"a":[5]}],"geometry":null,"properties":null,"type":"Feature"}"#,
+                "coordinates",
+            ),
+            (
+                r#"{"features":[],"geometry":{"coordinates":[1,2],"type":"Point","id":3,"features":[{"type":1}]},"type":"FeatureCollection"}"#,
+                "geometry",
+            ),
+            (
+                r#"{"coordinates":[1,2],"features":[{"geometry":{"coordinates":[[1,2]],"type":"LineString"},"type":"Feature"},[1]],"type":"Point"}"#,
+                "features",
+            ),
+        ];
+        for (input, name) in inputs {
+            let expected: JsonValue = serde_json::from_str(input).unwrap();
+            let foreign_members = match input.parse::<GeoJson>().unwrap() {
+                GeoJson::Geometry(geometry) => geometry.foreign_members,
+                GeoJson::Feature(feature) => feature.foreign_members,
+                GeoJson::FeatureCollection(collection) => collection.foreign_members,
+            };
+            // Integers would have become floats if they'd been read as coordinates.
+            assert_eq!(foreign_members.unwrap()[name], expected[name]);
+            check(expected).unwrap();
+        }
+    }
+
+    #[test]
+    fn type_last_is_streamed() {
+        // The crate writes members in order of their names, so `type` comes last.
+        let geojson_str = check(json!({
+            "type": "FeatureCollection",
+            "features": [{
+                "type": "Feature",
+                "properties": null,
+                "geometry": { "type": "LineString", "coordinates": [[1, 2], [3.5, 4]] }
+            }]
+        }))
+        .unwrap()
+        .to_string();
+        assert!(geojson_str.ends_with(r#""type":"FeatureCollection"}"#));
+
+        let object: GeoJsonObject = serde_json::from_str(&geojson_str).unwrap();
+        let features = match object.features {
+            Some(ArrayOr::Array(features)) => features,
+            _ => panic!("features weren't read as an array"),
+        };
+        let feature = match features.into_iter().next() {
+            Some(ObjectOr::Object(feature)) => feature,
+            _ => panic!("the feature wasn't read as an object"),
+        };
+        let geometry = match feature.geometry {
+            Some(ObjectOr::Object(geometry)) => geometry,
+            _ => panic!("the geometry wasn't read as an object"),
+        };
+        match geometry.coordinates {
+            Some(Coordinates::Array(positions)) => {
+                assert_eq!(positions.len(), 2);
+                assert!(positions
+                    .iter()
+                    .all(|position| matches!(position, Coordinates::Position(_, _))));
+            }
+            _ => panic!("the coordinates weren't read as positions"),
+        }
+    }
+
+    #[test]
+    fn same_errors() {
+        let invalid = [
+            json!({ "coordinates": [1, 2] }),
+            json!({ "type": 1, "coordinates": [1, 2] }),
+            json!({ "type": "Pointy", "coordinates": [1, 2] }),
+            json!({ "type": "Point" }),
+            json!({ "type": "Point", "coordinates": [1] }),
+            json!({ "type": "Point", "coordinates": [[1, 2]] }),
+            json!({ "type": "Point", "coordinates": [[1, 2], [3, 4]] }),
+            json!({ "type": "Point", "coordinates": [1, "2"] }),
+            json!({ "type": "Point", "coordinates": null }),
+            json!({ "type": "LineString", "coordinates": [1, 2] }),
+            json!({ "type": "LineString", "coordinates": [[1, 2], [3]] }),
+            json!({ "type": "Polygon", "coordinates": [[1, 2]] }),
+            json!({ "type": "MultiPolygon", "coordinates": [[[1, 2]]] }),
+            json!({ "type": "GeometryCollection" }),
+            json!({ "type": "GeometryCollection", "geometries": {} }),
+            json!({ "type": "GeometryCollection", "geometries": [1] }),
+            json!({ "type": "Point", "coordinates": [1, 2], "bbox": 1 }),
+            json!({ "type": "Point", "coordinates": [1, 2], "bbox": [1, "2"] }),
+            json!({ "type": "Feature" }),
+            json!({ "type": "Feature", "geometry": 1 }),
+            json!({ "type": "Feature", "geometry": null, "properties": 1 }),
+            json!({ "type": "Feature", "geometry": null, "id": null }),
+            json!({ "type": "FeatureCollection" }),
+            json!({ "type": "FeatureCollection", "features": null }),
+            json!({ "type": "FeatureCollection", "features": [[]] }),
+        ];
+        for value in invalid {
+            assert!(check(value).is_err());
+        }
+    }
+}
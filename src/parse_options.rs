@@ -0,0 +1,598 @@
+use crate::Error;
+
+use std::io;
+
+/// Limits on the size of the GeoJSON which is parsed, for reading untrusted input, and whether
+/// errors report where they were found.
+///
+/// The limits are checked on the JSON text as it's read, so input which exceeds one is rejected
+/// before it has been buffered or parsed, with a dedicated [`Error`] variant. Each limit is `None`,
+/// i.e. unlimited, by default.
+///
+/// The options are used by [`GeoJson::from_reader_with_options`](crate::GeoJson::from_reader_with_options)
+/// and [`FeatureReader::with_options`](crate::FeatureReader::with_options).
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Error, GeoJson, ParseOptions};
+///
+/// let options = ParseOptions {
+///     max_positions: Some(2),
+///     ..ParseOptions::default()
+/// };
+///
+/// let geojson_str = r#"{ "type": "LineString", "coordinates": [[1, 2], [3, 4], [5, 6]] }"#;
+/// let err = GeoJson::from_reader_with_options(geojson_str.as_bytes(), &options).unwrap_err();
+/// assert!(matches!(err, Error::PositionLimitExceeded(2)));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// The maximum nesting depth of JSON objects and arrays.
+    pub max_depth: Option<usize>,
+    /// The maximum number of positions in a single geometry, counting every geometry in a
+    /// GeometryCollection.
+    pub max_positions: Option<usize>,
+    /// The maximum number of features in a FeatureCollection.
+    pub max_features: Option<usize>,
+    /// The maximum length in bytes of the JSON text of the `properties` of a Feature.
+    pub max_property_bytes: Option<usize>,
+    /// Wrap errors in [`Error::Located`], which records where in the input they were found, e.g.
+    /// as a JSON pointer like `/features/3/geometry/coordinates/0`. Off by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Error, GeoJson, ParseOptions};
+    ///
+    /// let options = ParseOptions {
+    ///     error_locations: true,
+    ///     ..ParseOptions::default()
+    /// };
+    ///
+    /// let geojson_str = r#"{ "type": "LineString", "coordinates": [[1, 2], [3]] }"#;
+    /// let err = GeoJson::from_reader_with_options(geojson_str.as_bytes(), &options).unwrap_err();
+    /// assert_eq!(err.location().unwrap().pointer, "/coordinates/1");
+    /// assert!(matches!(err.without_location(), Error::PositionTooShort(1)));
+    /// ```
+    pub error_locations: bool,
+}
+
+/// A limit of [`ParseOptions`] which was exceeded.
+#[derive(Clone, Copy, Debug)]
+enum Exceeded {
+    Depth(usize),
+    Positions(usize),
+    Features(usize),
+    PropertyBytes(usize),
+}
+
+/// An array or object which the scanner is inside of.
+#[derive(Default)]
+struct Container {
+    is_object: bool,
+    /// The name of the member whose value is being read, for objects.
+    key: Option<Vec<u8>>,
+    /// Whether the next non-whitespace byte starts a value, i.e. after `[`, `,` in an array, or
+    /// `:` in an object.
+    expecting_value: bool,
+    /// Whether this is, or is inside of, the `coordinates` member of a geometry.
+    in_coordinates: bool,
+    /// Whether this is the `features` array of the top-level object.
+    is_features: bool,
+    /// Whether a number has been seen in this array, which makes it a position.
+    has_number: bool,
+    /// Whether this object may be a Feature, i.e. it's the top-level object or an element of
+    /// `features`.
+    may_be_feature: bool,
+    /// Whether this object may be a geometry, i.e. it's the top-level object, the `geometry` of
+    /// a Feature, or an element of the `geometries` of a geometry.
+    may_be_geometry: bool,
+    /// Whether this is the `geometries` array of a geometry.
+    is_geometries: bool,
+}
+
+/// Tracks the structure of JSON text one byte at a time, to check it against [`ParseOptions`]
+/// without parsing it.
+///
+/// The text isn't validated, which is left to the parser.
+pub(crate) struct LimitScanner {
+    options: ParseOptions,
+    stack: Vec<Container>,
+    /// Whether the top-level value has started.
+    started: bool,
+    in_string: bool,
+    in_key: bool,
+    escaped: bool,
+    /// The value of a `\uXXXX` escape so far, and the number of its hex digits which are still
+    /// to be read.
+    unicode_escape: Option<(u32, u8)>,
+    /// The name of the member being read, with escapes decoded, since e.g. `"feature\u0073"`
+    /// names the `features` member just as `"features"` does.
+    key: Vec<u8>,
+    positions: usize,
+    features: usize,
+    /// The depth of the object whose `properties` are being read, and their length so far.
+    properties: Option<(usize, usize)>,
+    exceeded: Option<Exceeded>,
+}
+
+/// Member names longer than this aren't of interest, so aren't kept.
+const MAX_KEY_LEN: usize = 16;
+
+impl LimitScanner {
+    pub(crate) fn new(options: ParseOptions) -> Self {
+        LimitScanner {
+            options,
+            stack: vec![],
+            started: false,
+            in_string: false,
+            in_key: false,
+            escaped: false,
+            unicode_escape: None,
+            key: vec![],
+            positions: 0,
+            features: 0,
+            properties: None,
+            exceeded: None,
+        }
+    }
+
+    /// The error for the limit which was exceeded, if any.
+    pub(crate) fn error(&self) -> Option<Error> {
+        self.exceeded.map(|exceeded| match exceeded {
+            Exceeded::Depth(max) => Error::DepthLimitExceeded(max),
+            Exceeded::Positions(max) => Error::PositionLimitExceeded(max),
+            Exceeded::Features(max) => Error::FeatureLimitExceeded(max),
+            Exceeded::PropertyBytes(max) => Error::PropertySizeLimitExceeded(max),
+        })
+    }
+
+    /// Scan bytes which were read, failing once a limit has been exceeded.
+    pub(crate) fn scan(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if self.exceeded.is_none() {
+            for &byte in bytes {
+                if let Err(exceeded) = self.scan_byte(byte) {
+                    self.exceeded = Some(exceeded);
+                    break;
+                }
+            }
+        }
+        match self.error() {
+            Some(err) => Err(io::Error::new(io::ErrorKind::InvalidData, err.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn scan_byte(&mut self, byte: u8) -> Result<(), Exceeded> {
+        if let Some((depth, len)) = &mut self.properties {
+            // Whitespace, `,` or `}` following the value at the level of its object aren't
+            // part of it.
+            if self.in_string
+                || self.stack.len() > *depth
+                || !(byte.is_ascii_whitespace() || byte == b',' || byte == b'}')
+            {
+                *len += 1;
+                check(
+                    *len,
+                    self.options.max_property_bytes,
+                    Exceeded::PropertyBytes,
+                )?;
+            }
+        }
+
+        if self.in_string {
+            let decoded = if let Some((value, remaining)) = self.unicode_escape {
+                // Invalid escapes are left to the parser to reject.
+                let digit = (byte as char).to_digit(16).unwrap_or(0);
+                let value = value << 4 | digit;
+                if remaining > 1 {
+                    self.unicode_escape = Some((value, remaining - 1));
+                    return Ok(());
+                }
+                self.unicode_escape = None;
+                // Member names of interest are ASCII, so any other character stands in for
+                // the rest.
+                Some(if value < 0x80 { value as u8 } else { 0x80 })
+            } else if self.escaped {
+                self.escaped = false;
+                match byte {
+                    b'u' => {
+                        self.unicode_escape = Some((0, 4));
+                        None
+                    }
+                    b'b' => Some(0x08),
+                    b'f' => Some(0x0c),
+                    b'n' => Some(b'\n'),
+                    b'r' => Some(b'\r'),
+                    b't' => Some(b'\t'),
+                    _ => Some(byte),
+                }
+            } else if byte == b'\\' {
+                self.escaped = true;
+                None
+            } else if byte == b'"' {
+                self.in_string = false;
+                if self.in_key {
+                    self.in_key = false;
+                    if let Some(container) = self.stack.last_mut() {
+                        container.key = Some(std::mem::take(&mut self.key));
+                    }
+                }
+                return Ok(());
+            } else {
+                Some(byte)
+            };
+            if let Some(decoded) = decoded {
+                if self.in_key && self.key.len() <= MAX_KEY_LEN {
+                    self.key.push(decoded);
+                }
+            }
+            return Ok(());
+        }
+
+        if byte.is_ascii_whitespace() {
+            return Ok(());
+        }
+        let expecting_value = match self.stack.last() {
+            Some(container) => container.expecting_value,
+            None => !self.started,
+        };
+        match byte {
+            b'}' | b']' => {
+                self.end_properties();
+                self.stack.pop();
+            }
+            b',' => {
+                self.end_properties();
+                if let Some(container) = self.stack.last_mut() {
+                    container.key = None;
+                    container.expecting_value = !container.is_object;
+                }
+            }
+            b':' => {
+                if let Some(container) = self.stack.last_mut() {
+                    container.expecting_value = true;
+                }
+            }
+            b'"' if !expecting_value => {
+                self.in_string = true;
+                self.in_key = true;
+                self.key.clear();
+            }
+            _ if expecting_value => self.start_value(byte)?,
+            // The rest of a number or literal.
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn start_value(&mut self, byte: u8) -> Result<(), Exceeded> {
+        self.started = true;
+        let depth = self.stack.len();
+        let (
+            key,
+            in_coordinates,
+            parent_is_features,
+            parent_may_be_feature,
+            parent_may_be_geometry,
+            parent_is_geometries,
+        ) = match self.stack.last_mut() {
+            Some(parent) => {
+                parent.expecting_value = false;
+                (
+                    parent.key.clone().filter(|_| parent.is_object),
+                    parent.in_coordinates,
+                    parent.is_features,
+                    parent.may_be_feature,
+                    parent.may_be_geometry,
+                    parent.is_geometries,
+                )
+            }
+            None => (None, false, false, false, false, false),
+        };
+        let key = key.as_deref();
+
+        if parent_is_features {
+            self.features += 1;
+            check(self.features, self.options.max_features, Exceeded::Features)?;
+        }
+        if parent_may_be_feature && key == Some(b"properties") && self.properties.is_none() {
+            self.properties = Some((depth, 1));
+            check(1, self.options.max_property_bytes, Exceeded::PropertyBytes)?;
+        }
+
+        match byte {
+            b'{' | b'[' => {
+                // Each top-level geometry has its own count of positions.
+                if byte == b'{'
+                    && (depth == 0
+                        || parent_is_features
+                        || (parent_may_be_feature && key == Some(b"geometry")))
+                {
+                    self.positions = 0;
+                }
+                self.stack.push(Container {
+                    is_object: byte == b'{',
+                    key: None,
+                    expecting_value: byte == b'[',
+                    in_coordinates: in_coordinates
+                        || (parent_may_be_geometry && key == Some(b"coordinates")),
+                    is_features: byte == b'[' && depth == 1 && key == Some(b"features"),
+                    has_number: false,
+                    may_be_feature: byte == b'{' && (depth == 0 || parent_is_features),
+                    may_be_geometry: byte == b'{'
+                        && (depth == 0
+                            || (parent_may_be_feature && key == Some(b"geometry"))
+                            || parent_is_geometries),
+                    is_geometries: byte == b'['
+                        && parent_may_be_geometry
+                        && key == Some(b"geometries"),
+                });
+                check(self.stack.len(), self.options.max_depth, Exceeded::Depth)?;
+            }
+            b'"' => self.in_string = true,
+            b'-' | b'0'..=b'9' => {
+                if let Some(parent) = self.stack.last_mut() {
+                    if parent.in_coordinates && !parent.is_object && !parent.has_number {
+                        parent.has_number = true;
+                        self.positions += 1;
+                        check(
+                            self.positions,
+                            self.options.max_positions,
+                            Exceeded::Positions,
+                        )?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Stop counting the length of `properties` at the end of the object they belong to, or of
+    /// their member.
+    fn end_properties(&mut self) {
+        if let Some((depth, _)) = self.properties {
+            if self.stack.len() == depth {
+                self.properties = None;
+            }
+        }
+    }
+}
+
+fn check(
+    value: usize,
+    max: Option<usize>,
+    exceeded: fn(usize) -> Exceeded,
+) -> Result<(), Exceeded> {
+    match max {
+        Some(max) if value > max => Err(exceeded(max)),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the bytes read from `reader` against [`ParseOptions`].
+pub(crate) struct LimitReader<R> {
+    reader: R,
+    pub(crate) scanner: LimitScanner,
+}
+
+impl<R> LimitReader<R> {
+    pub(crate) fn new(reader: R, options: ParseOptions) -> Self {
+        LimitReader {
+            reader,
+            scanner: LimitScanner::new(options),
+        }
+    }
+}
+
+impl<R: io::Read> io::Read for LimitReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.reader.read(buf)?;
+        self.scanner.scan(&buf[..len])?;
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FeatureReader, GeoJson};
+
+    fn parse(s: &str, options: ParseOptions) -> crate::Result<GeoJson> {
+        GeoJson::from_reader_with_options(s.as_bytes(), &options)
+    }
+
+    fn feature_collection() -> &'static str {
+        r#"{
+            "type": "FeatureCollection",
+            "features": [
+                { "type": "Feature", "properties": { "name": "a", "nested": { "properties": [] } },
+                  "geometry": { "type": "GeometryCollection", "geometries": [
+                      { "type": "Point", "coordinates": [1, 2],
+                        "properties": "a foreign member, which isn't limited like properties" },
+                      { "type": "LineString", "coordinates": [[1, 2], [-3, 4.5]] }
+                  ] } },
+                { "properties": null, "type": "Feature",
+                  "geometry": { "type": "MultiPolygon", "coordinates": [[[[0, 0], [1, 0], [1, 1], [0, 0]]]] } }
+            ]
+        }"#
+    }
+
+    #[test]
+    fn within_limits() {
+        let options = ParseOptions {
+            max_depth: Some(8),
+            max_positions: Some(4),
+            max_features: Some(2),
+            max_property_bytes: Some(47),
+            error_locations: false,
+        };
+        let expected: GeoJson = feature_collection().parse().unwrap();
+        assert_eq!(
+            parse(feature_collection(), options.clone()).unwrap(),
+            expected
+        );
+
+        let features = FeatureReader::from_reader(feature_collection().as_bytes())
+            .with_options(options)
+            .features()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(features.len(), 2);
+    }
+
+    #[test]
+    fn limits_exceeded() {
+        let err = parse(
+            feature_collection(),
+            ParseOptions {
+                max_depth: Some(7),
+                ..ParseOptions::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::DepthLimitExceeded(7)));
+
+        let err = parse(
+            feature_collection(),
+            ParseOptions {
+                max_positions: Some(3),
+                ..ParseOptions::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::PositionLimitExceeded(3)));
+
+        let err = parse(
+            feature_collection(),
+            ParseOptions {
+                max_features: Some(1),
+                ..ParseOptions::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::FeatureLimitExceeded(1)));
+
+        let err = parse(
+            feature_collection(),
+            ParseOptions {
+                max_property_bytes: Some(46),
+                ..ParseOptions::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::PropertySizeLimitExceeded(46)));
+    }
+
+    #[test]
+    fn feature_reader_limits() {
+        let options = ParseOptions {
+            max_positions: Some(3),
+            error_locations: true,
+            ..ParseOptions::default()
+        };
+        let mut features = FeatureReader::from_reader(feature_collection().as_bytes())
+            .with_options(options)
+            .features();
+        assert!(features.next().unwrap().is_ok());
+        let err = features.next().unwrap().unwrap_err();
+        assert_eq!(err.location().unwrap().feature_index, Some(1));
+        assert!(matches!(
+            err.without_location(),
+            Error::PositionLimitExceeded(3)
+        ));
+        assert!(features.next().is_none());
+
+        let options = ParseOptions {
+            max_features: Some(1),
+            ..ParseOptions::default()
+        };
+        let results: Vec<_> = FeatureReader::from_reader(feature_collection().as_bytes())
+            .with_options(options)
+            .features()
+            .collect();
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[1], Err(Error::FeatureLimitExceeded(1))));
+    }
+
+    #[test]
+    fn escaped_member_names() {
+        let options = ParseOptions {
+            max_positions: Some(1),
+            max_features: Some(1),
+            max_property_bytes: Some(4),
+            ..ParseOptions::default()
+        };
+        let check_exceeded = |s: &str, expected: fn(&Error) -> bool| {
+            let err = parse(s, options.clone()).unwrap_err();
+            assert!(expected(&err), "{:?}", err);
+            let err = FeatureReader::from_reader(s.as_bytes())
+                .with_options(options.clone())
+                .features()
+                .find_map(|feature| feature.err())
+                .unwrap();
+            assert!(expected(&err), "{:?}", err);
+        };
+
+        check_exceeded(
+            r#"{ "type": "FeatureCollection", "features": [
+                { "type": "Feature", "properties": null,
+                  "geometry": { "type": "LineString", "coordin\u0061tes": [[1, 2], [3, 4]] } }
+            ] }"#,
+            |err| matches!(err, Error::PositionLimitExceeded(1)),
+        );
+        check_exceeded(
+            r#"{ "type": "FeatureCollection", "feature\u0073": [
+                { "type": "Feature", "properties": null, "geometry": null },
+                { "type": "Feature", "properties": null, "geometry": null }
+            ] }"#,
+            |err| matches!(err, Error::FeatureLimitExceeded(1)),
+        );
+        check_exceeded(
+            r#"{ "type": "FeatureCollection", "features": [
+                { "type": "Feature", "propertie\u0073": { "name": "a" }, "geometry": null }
+            ] }"#,
+            |err| matches!(err, Error::PropertySizeLimitExceeded(4)),
+        );
+        check_exceeded(
+            r#"{ "type": "FeatureCollection", "features": [
+                { "type": "Feature", "\u0070\u0072\u006f\u0070\u0065\u0072\u0074\u0069\u0065\u0073": { "name": "a" }, "geometry": null }
+            ] }"#,
+            |err| matches!(err, Error::PropertySizeLimitExceeded(4)),
+        );
+    }
+
+    #[test]
+    fn coordinates_outside_of_geometries() {
+        let options = ParseOptions {
+            max_positions: Some(1),
+            ..ParseOptions::default()
+        };
+        let geojson_str = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                { "type": "Feature",
+                  "properties": { "coordinates": [[1, 2], [3, 4]] },
+                  "coordinates": [[1, 2], [3, 4]],
+                  "geometry": { "type": "Point", "coordinates": [1, 2],
+                                "extra": { "coordinates": [[1, 2], [3, 4]] } } }
+            ]
+        }"#;
+        assert!(parse(geojson_str, options.clone()).is_ok());
+        let features = FeatureReader::from_reader(geojson_str.as_bytes())
+            .with_options(options.clone())
+            .features()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(features.len(), 1);
+
+        let geojson_str = r#"{ "type": "GeometryCollection", "geometries": [
+            { "type": "Point", "coordinates": [1, 2] },
+            { "type": "Point", "coordinates": [3, 4] }
+        ] }"#;
+        let err = parse(geojson_str, options).unwrap_err();
+        assert!(matches!(err, Error::PositionLimitExceeded(1)));
+    }
+}
@@ -0,0 +1,211 @@
+//! Access to the values of a [`Position`] by name.
+//!
+//! [RFC 7946](https://tools.ietf.org/html/rfc7946#section-3.1.1) gives the first two values of a
+//! position as longitude and latitude, or easting and northing, and the optional third as the
+//! altitude or elevation. It leaves any further values undefined, but some producers write a
+//! measure (M) value as a fourth, as in WKT's `POINT ZM`. This crate reads positions the same way:
+//!
+//! - 2 values are X and Y.
+//! - 3 values are X, Y and Z. A third value is always Z, never M, since a position without Z
+//!   can't be told apart from one without M.
+//! - 4 values are X, Y, Z and M.
+//!
+//! Positions with fewer than 2 or more than 4 values have no [`Dimensions`].
+use crate::errors::{Error, Result};
+use crate::Position;
+
+/// Which values a position holds, by the number of them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dimensions {
+    Xy,
+    Xyz,
+    Xyzm,
+}
+
+impl Dimensions {
+    /// The number of values in a position with these dimensions.
+    pub fn num_values(self) -> usize {
+        match self {
+            Dimensions::Xy => 2,
+            Dimensions::Xyz => 3,
+            Dimensions::Xyzm => 4,
+        }
+    }
+
+    pub fn has_z(self) -> bool {
+        self != Dimensions::Xy
+    }
+
+    pub fn has_m(self) -> bool {
+        self == Dimensions::Xyzm
+    }
+}
+
+/// Named accessors for the values of a position.
+///
+/// This is implemented for `[f64]`, so it can be used with a [`Position`], an
+/// [`InlinePosition`](crate::InlinePosition) or the positions of a
+/// [`FlatValue`](crate::FlatValue).
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Dimensions, IntoPosition, Position, PositionExt};
+///
+/// let position: Position = (1.0, 2.0, 3.0).into_position();
+/// assert_eq!(position, vec![1.0, 2.0, 3.0]);
+/// assert_eq!((position.x(), position.y()), (1.0, 2.0));
+/// assert_eq!(position.z(), Some(3.0));
+/// assert_eq!(position.m(), None);
+/// assert_eq!(position.dimensions().unwrap(), Dimensions::Xyz);
+///
+/// let position = [1.0, 2.0, 3.0, 4.0].into_position();
+/// assert_eq!(position.m(), Some(4.0));
+/// assert!(vec![1.0].dimensions().is_err());
+/// ```
+pub trait PositionExt {
+    /// The first value, e.g. the longitude.
+    ///
+    /// # Panics
+    ///
+    /// If the position is empty.
+    fn x(&self) -> f64;
+
+    /// The second value, e.g. the latitude.
+    ///
+    /// # Panics
+    ///
+    /// If the position has fewer than 2 values.
+    fn y(&self) -> f64;
+
+    /// The third value, e.g. the altitude, if there is one.
+    fn z(&self) -> Option<f64>;
+
+    /// The fourth value, if there is one, which is taken to be a measure.
+    fn m(&self) -> Option<f64>;
+
+    /// Which values the position holds, or an error if it has fewer than 2 or more than 4.
+    fn dimensions(&self) -> Result<Dimensions>;
+}
+
+impl PositionExt for [f64] {
+    fn x(&self) -> f64 {
+        self[0]
+    }
+
+    fn y(&self) -> f64 {
+        self[1]
+    }
+
+    fn z(&self) -> Option<f64> {
+        self.get(2).copied()
+    }
+
+    fn m(&self) -> Option<f64> {
+        self.get(3).copied()
+    }
+
+    fn dimensions(&self) -> Result<Dimensions> {
+        match self.len() {
+            0 | 1 => Err(Error::PositionTooShort(self.len())),
+            2 => Ok(Dimensions::Xy),
+            3 => Ok(Dimensions::Xyz),
+            4 => Ok(Dimensions::Xyzm),
+            len => Err(Error::PositionTooLong(len)),
+        }
+    }
+}
+
+/// Conversion of tuples and arrays of 2, 3 or 4 values, in the order X, Y, Z, M, into a
+/// [`Position`].
+pub trait IntoPosition {
+    fn into_position(self) -> Position;
+}
+
+impl IntoPosition for (f64, f64) {
+    fn into_position(self) -> Position {
+        vec![self.0, self.1]
+    }
+}
+
+impl IntoPosition for (f64, f64, f64) {
+    fn into_position(self) -> Position {
+        vec![self.0, self.1, self.2]
+    }
+}
+
+impl IntoPosition for (f64, f64, f64, f64) {
+    fn into_position(self) -> Position {
+        vec![self.0, self.1, self.2, self.3]
+    }
+}
+
+impl IntoPosition for [f64; 2] {
+    fn into_position(self) -> Position {
+        self.to_vec()
+    }
+}
+
+impl IntoPosition for [f64; 3] {
+    fn into_position(self) -> Position {
+        self.to_vec()
+    }
+}
+
+impl IntoPosition for [f64; 4] {
+    fn into_position(self) -> Position {
+        self.to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FlatValue, InlinePosition, Value};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn accessors() {
+        let position = (1.0, 2.0, 3.0, 4.0).into_position();
+        assert_eq!(position.x(), 1.0);
+        assert_eq!(position.y(), 2.0);
+        assert_eq!(position.z(), Some(3.0));
+        assert_eq!(position.m(), Some(4.0));
+
+        let position = InlinePosition::from([1.0, 2.0]);
+        assert_eq!((position.x(), position.y()), (1.0, 2.0));
+        assert_eq!(position.z(), None);
+        assert_eq!(position.m(), None);
+
+        let flat = FlatValue::try_from(Value::LineString(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+        ]))
+        .unwrap();
+        let z: Vec<_> = flat.positions().map(|position| position.z()).collect();
+        assert_eq!(z, [Some(3.0), Some(6.0)]);
+    }
+
+    #[test]
+    fn dimensions() {
+        let dimensions = |position: Position| position.dimensions();
+        assert!(matches!(
+            dimensions(vec![]),
+            Err(Error::PositionTooShort(0))
+        ));
+        assert!(matches!(
+            dimensions(vec![1.0]),
+            Err(Error::PositionTooShort(1))
+        ));
+        assert_eq!(dimensions(vec![1.0, 2.0]).unwrap(), Dimensions::Xy);
+        assert_eq!(dimensions(vec![1.0, 2.0, 3.0]).unwrap(), Dimensions::Xyz);
+        let xyzm = dimensions(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!(xyzm, Dimensions::Xyzm);
+        assert_eq!(xyzm.num_values(), 4);
+        assert!(xyzm.has_z() && xyzm.has_m());
+        assert!(matches!(
+            dimensions(vec![1.0; 5]),
+            Err(Error::PositionTooLong(5))
+        ));
+    }
+}
@@ -96,7 +96,7 @@
 //!     ...
 //! }
 //! ```
-use crate::{JsonObject, JsonValue, Result};
+use crate::{JsonObject, JsonValue, Result, WriteOptions};
 
 use serde::{ser::Error, Serialize, Serializer};
 
@@ -137,7 +137,26 @@ pub fn to_feature_collection_string<T>(values: &[T]) -> Result<String>
 where
     T: Serialize,
 {
-    let vec = to_feature_collection_byte_vec(values)?;
+    to_feature_collection_string_with_options(values, &WriteOptions::default())
+}
+
+/// Serialize elements to a GeoJSON FeatureCollection string, rounding coordinates as `options`
+/// ask for.
+///
+/// Note that `T` must have a column called `geometry`.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail, or if `T` contains a map with non-string keys.
+pub fn to_feature_collection_string_with_options<T>(
+    values: &[T],
+    options: &WriteOptions,
+) -> Result<String>
+where
+    T: Serialize,
+{
+    let vec = to_feature_collection_byte_vec_with_options(values, options)?;
     let string = unsafe {
         // We do not emit invalid UTF-8.
         String::from_utf8_unchecked(vec)
@@ -171,11 +190,30 @@ where
 /// Serialization can fail if `T`'s implementation of `Serialize` decides to
 /// fail, or if `T` contains a map with non-string keys.
 pub fn to_feature_collection_byte_vec<T>(values: &[T]) -> Result<Vec<u8>>
+where
+    T: Serialize,
+{
+    to_feature_collection_byte_vec_with_options(values, &WriteOptions::default())
+}
+
+/// Serialize elements to a GeoJSON FeatureCollection byte vector, rounding coordinates as
+/// `options` ask for.
+///
+/// Note that `T` must have a column called `geometry`.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail, or if `T` contains a map with non-string keys.
+pub fn to_feature_collection_byte_vec_with_options<T>(
+    values: &[T],
+    options: &WriteOptions,
+) -> Result<Vec<u8>>
 where
     T: Serialize,
 {
     let mut writer = Vec::with_capacity(128);
-    to_feature_collection_writer(&mut writer, values)?;
+    to_feature_collection_writer_with_options(&mut writer, values, options)?;
     Ok(writer)
 }
 
@@ -192,7 +230,8 @@ where
     W: io::Write,
     T: Serialize,
 {
-    let feature_serializer = FeatureWrapper::new(value);
+    let options = WriteOptions::default();
+    let feature_serializer = FeatureWrapper::new(value, &options);
     let mut serializer = serde_json::Serializer::new(writer);
     feature_serializer.serialize(&mut serializer)?;
     Ok(())
@@ -207,6 +246,58 @@ where
 /// Serialization can fail if `T`'s implementation of `Serialize` decides to
 /// fail, or if `T` contains a map with non-string keys.
 pub fn to_feature_collection_writer<W, T>(writer: W, features: &[T]) -> Result<()>
+where
+    W: io::Write,
+    T: Serialize,
+{
+    to_feature_collection_writer_with_options(writer, features, &WriteOptions::default())
+}
+
+/// Serialize elements as a GeoJSON FeatureCollection into the IO stream, rounding coordinates as
+/// `options` ask for.
+///
+/// Only the coordinates of each `geometry`, and its bbox if [`WriteOptions::round_bbox`] is set,
+/// are rounded. The numbers in properties are written as they are.
+///
+/// Note that `T` must have a column called `geometry`.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Geometry, Value, WriteOptions};
+///
+/// #[derive(serde::Serialize)]
+/// struct MyStruct {
+///     geometry: Geometry,
+///     area: f64,
+/// }
+///
+/// let my_structs = vec![MyStruct {
+///     geometry: Geometry::new(Value::Point(vec![11.123456789, 22.2])),
+///     area: 0.123456789,
+/// }];
+/// let options = WriteOptions {
+///     coordinate_precision: Some(6),
+///     ..WriteOptions::default()
+/// };
+///
+/// let mut output = vec![];
+/// geojson::ser::to_feature_collection_writer_with_options(&mut output, &my_structs, &options)
+///     .unwrap();
+/// let geojson_string = String::from_utf8(output).unwrap();
+/// assert!(geojson_string.contains(r#""coordinates":[11.123457,22.2]"#));
+/// assert!(geojson_string.contains(r#""area":0.123456789"#));
+/// ```
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail, or if `T` contains a map with non-string keys.
+pub fn to_feature_collection_writer_with_options<W, T>(
+    writer: W,
+    features: &[T],
+    options: &WriteOptions,
+) -> Result<()>
 where
     W: io::Write,
     T: Serialize,
@@ -216,7 +307,7 @@ where
     let mut ser = serde_json::Serializer::new(writer);
     let mut map = ser.serialize_map(Some(2))?;
     map.serialize_entry("type", "FeatureCollection")?;
-    map.serialize_entry("features", &Features::new(features))?;
+    map.serialize_entry("features", &Features::new(features, options))?;
     map.end()?;
     Ok(())
 }
@@ -269,14 +360,15 @@ where
     T: Serialize,
 {
     features: &'a [T],
+    options: &'a WriteOptions,
 }
 
 impl<'a, T> Features<'a, T>
 where
     T: Serialize,
 {
-    fn new(features: &'a [T]) -> Self {
-        Self { features }
+    fn new(features: &'a [T], options: &'a WriteOptions) -> Self {
+        Self { features, options }
     }
 }
 
@@ -291,19 +383,21 @@ where
         use serde::ser::SerializeSeq;
         let mut seq = serializer.serialize_seq(None)?;
         for feature in self.features.iter() {
-            seq.serialize_element(&FeatureWrapper::new(feature))?;
+            seq.serialize_element(&FeatureWrapper::new(feature, self.options))?;
         }
         seq.end()
     }
 }
 
-struct FeatureWrapper<'t, T> {
+/// A struct with a `geometry` field, serialized as a Feature.
+pub(crate) struct FeatureWrapper<'t, T> {
     feature: &'t T,
+    options: &'t WriteOptions,
 }
 
 impl<'t, T> FeatureWrapper<'t, T> {
-    fn new(feature: &'t T) -> Self {
-        Self { feature }
+    pub(crate) fn new(feature: &'t T, options: &'t WriteOptions) -> Self {
+        Self { feature, options }
     }
 }
 
@@ -315,27 +409,39 @@ where
     where
         S: Serializer,
     {
-        let mut json_object: JsonObject = {
-            let value = serde_json::to_value(self.feature).map_err(|e| {
-                S::Error::custom(format!("Feature was not serializable as JSON - {}", e))
-            })?;
-            match value {
-                JsonValue::Object(object) => object,
-                JsonValue::Null => {
-                    return Err(S::Error::custom("expected JSON object but found `null`"))
-                }
-                JsonValue::Bool(_) => {
-                    return Err(S::Error::custom("expected JSON object but found `bool`"))
-                }
-                JsonValue::Number(_) => {
-                    return Err(S::Error::custom("expected JSON object but found `number`"))
-                }
-                JsonValue::String(_) => {
-                    return Err(S::Error::custom("expected JSON object but found `string`"))
-                }
-                JsonValue::Array(_) => {
-                    return Err(S::Error::custom("expected JSON object but found `array`"))
-                }
+        let value = serde_json::to_value(self.feature).map_err(|e| {
+            S::Error::custom(format!("Feature was not serializable as JSON - {}", e))
+        })?;
+        FeatureMembers::new(value, self.options)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+/// The members of the Feature which [`FeatureWrapper`] writes for a struct.
+pub(crate) struct FeatureMembers {
+    pub(crate) geometry: Option<JsonValue>,
+    properties: JsonObject,
+}
+
+impl FeatureMembers {
+    /// Split the JSON of a struct into its `geometry`, rounded as `options` ask, and the rest of
+    /// its fields, which are the properties.
+    pub(crate) fn new(value: JsonValue, options: &WriteOptions) -> serde_json::Result<Self> {
+        let mut json_object = match value {
+            JsonValue::Object(object) => object,
+            JsonValue::Null => return Err(Error::custom("expected JSON object but found `null`")),
+            JsonValue::Bool(_) => {
+                return Err(Error::custom("expected JSON object but found `bool`"))
+            }
+            JsonValue::Number(_) => {
+                return Err(Error::custom("expected JSON object but found `number`"))
+            }
+            JsonValue::String(_) => {
+                return Err(Error::custom("expected JSON object but found `string`"))
+            }
+            JsonValue::Array(_) => {
+                return Err(Error::custom("expected JSON object but found `array`"))
             }
         };
 
@@ -346,15 +452,29 @@ where
             //
             // We could just silently blunder on and set `geometry` to None in that case, but
             // printing a specific error message seems more likely to be helpful.
-            return Err(S::Error::custom("missing `geometry` field"));
+            return Err(Error::custom("missing `geometry` field"));
+        }
+        let mut geometry = json_object.remove("geometry");
+        if let Some(geometry) = &mut geometry {
+            options.round_geometry_json(geometry);
         }
-        let geometry = json_object.remove("geometry");
+        Ok(FeatureMembers {
+            geometry,
+            properties: json_object,
+        })
+    }
+}
 
+impl Serialize for FeatureMembers {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
         use serde::ser::SerializeMap;
         let mut map = serializer.serialize_map(Some(3))?;
         map.serialize_entry("type", "Feature")?;
-        map.serialize_entry("geometry", &geometry)?;
-        map.serialize_entry("properties", &json_object)?;
+        map.serialize_entry("geometry", &self.geometry)?;
+        map.serialize_entry("properties", &self.properties)?;
         map.end()
     }
 }
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::errors::{Error, Result};
-use crate::{feature, Bbox, Feature, Geometry, Position, Value};
+use crate::{feature, Bbox, Feature, GeoJson, Geometry, Position, Value};
 use crate::{JsonObject, JsonValue};
 
 pub fn expect_type(value: &mut JsonObject) -> Result<String> {
@@ -49,7 +49,7 @@ fn expect_property(obj: &mut JsonObject, name: &'static str) -> Result<JsonValue
     }
 }
 
-fn expect_owned_array(value: JsonValue) -> Result<Vec<JsonValue>> {
+pub(crate) fn expect_owned_array(value: JsonValue) -> Result<Vec<JsonValue>> {
     match value {
         JsonValue::Array(v) => Ok(v),
         _ => match value {
@@ -77,19 +77,21 @@ pub fn get_coords_value(object: &mut JsonObject) -> Result<JsonValue> {
 
 /// Used by FeatureCollection, Feature, Geometry
 pub fn get_bbox(object: &mut JsonObject) -> Result<Option<Bbox>> {
-    let bbox_json = match object.remove("bbox") {
-        Some(b) => b,
-        None => return Ok(None),
-    };
+    match object.remove("bbox") {
+        Some(bbox_json) => Ok(Some(json_to_bbox(bbox_json)?)),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn json_to_bbox(bbox_json: JsonValue) -> Result<Bbox> {
     let bbox_array = match bbox_json {
         JsonValue::Array(a) => a,
         _ => return Err(Error::BboxExpectedArray(bbox_json)),
     };
-    let bbox = bbox_array
+    bbox_array
         .into_iter()
         .map(|i| i.as_f64().ok_or(Error::BboxExpectedNumericValues(i)))
-        .collect::<Result<Vec<_>>>()?;
-    Ok(Some(bbox))
+        .collect::<Result<Vec<_>>>()
 }
 
 /// Used by FeatureCollection, Feature, Geometry
@@ -195,6 +197,18 @@ pub fn get_geometry(object: &mut JsonObject) -> Result<Option<Geometry>> {
     }
 }
 
+/// Used by FeatureSeqReader, FeatureLinesReader
+pub(crate) fn feature_from_feature_or_geometry(value: JsonValue) -> Result<Feature> {
+    match GeoJson::from_json_value(value)? {
+        GeoJson::Feature(feature) => Ok(feature),
+        GeoJson::Geometry(geometry) => Ok(Feature::from(geometry)),
+        GeoJson::FeatureCollection(_) => Err(Error::ExpectedType {
+            expected: "Feature".to_string(),
+            actual: "FeatureCollection".to_string(),
+        }),
+    }
+}
+
 /// Used by FeatureCollection
 pub fn get_features(object: &mut JsonObject) -> Result<Vec<Feature>> {
     let prop = expect_property(object, "features")?;
@@ -246,3 +260,72 @@ fn json_to_3d_positions(json: &JsonValue) -> Result<Vec<Vec<Vec<Position>>>> {
     }
     Ok(coords)
 }
+
+/// The range of the X, Y and Z values of a set of positions.
+///
+/// Any values after Z are measures, as in [`PositionExt`](crate::PositionExt), so aren't part of
+/// the range, and Z is only part of it if every position has one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Extent {
+    pub(crate) min: Vec<f64>,
+    pub(crate) max: Vec<f64>,
+}
+
+impl Extent {
+    pub(crate) fn add(&mut self, position: &[f64]) {
+        let dimensions = position.len().min(3);
+        if dimensions == 0 {
+            return;
+        }
+        if self.min.is_empty() {
+            self.min.extend_from_slice(&position[..dimensions]);
+            self.max.extend_from_slice(&position[..dimensions]);
+            return;
+        }
+        self.min.truncate(dimensions);
+        self.max.truncate(dimensions);
+        for (dimension, &value) in position.iter().take(self.min.len()).enumerate() {
+            self.min[dimension] = self.min[dimension].min(value);
+            self.max[dimension] = self.max[dimension].max(value);
+        }
+    }
+
+    pub(crate) fn union(&mut self, other: &Extent) {
+        if !other.min.is_empty() {
+            self.add(&other.min);
+            self.add(&other.max);
+        }
+    }
+
+    /// Add every position of `value`, including those of the geometries of a
+    /// GeometryCollection.
+    pub(crate) fn add_value(&mut self, value: &Value) {
+        match value {
+            Value::Point(position) => self.add(position),
+            Value::MultiPoint(positions) | Value::LineString(positions) => {
+                positions.iter().for_each(|position| self.add(position))
+            }
+            Value::MultiLineString(lines) | Value::Polygon(lines) => lines
+                .iter()
+                .flatten()
+                .for_each(|position| self.add(position)),
+            Value::MultiPolygon(polygons) => polygons
+                .iter()
+                .flatten()
+                .flatten()
+                .for_each(|position| self.add(position)),
+            Value::GeometryCollection(geometries) => geometries
+                .iter()
+                .for_each(|geometry| self.add_value(&geometry.value)),
+        }
+    }
+
+    /// The bbox of the positions, i.e. the minimum of each dimension followed by the maximum, or
+    /// `None` if there were no positions.
+    pub(crate) fn to_bbox(&self) -> Option<Bbox> {
+        if self.min.is_empty() {
+            return None;
+        }
+        Some(self.min.iter().chain(&self.max).copied().collect())
+    }
+}
@@ -0,0 +1,623 @@
+//! Check GeoJSON objects for conformance with [RFC 7946](https://tools.ietf.org/html/rfc7946).
+//!
+//! Parsing is lenient: a [`GeoJson`] may hold a LineString with a single position, an unclosed
+//! polygon ring, or a longitude of 200. [`validate`] checks an object for these problems, and
+//! reports every one it finds as a [`Diagnostic`], with the JSON pointer of the offending member.
+//! Problems which make the object invalid GeoJSON are [errors](Severity::Error), while those the
+//! specification only recommends against, like the winding order of polygon rings, are
+//! [warnings](Severity::Warning).
+//!
+//! The features of a large FeatureCollection can be checked as they're read with
+//! [`validate_feature_reader`].
+//!
+//! # Examples
+//!
+//! ```
+//! use geojson::validate::{validate, Problem, Severity};
+//! use geojson::GeoJson;
+//!
+//! let geojson: GeoJson = r#"{
+//!     "type": "Feature",
+//!     "geometry": {
+//!         "type": "Polygon",
+//!         "coordinates": [[[0, 0], [0, 1], [1, 1], [1, 0], [0, 0]]]
+//!     },
+//!     "properties": null,
+//!     "crs": { "type": "name", "properties": { "name": "EPSG:4326" } }
+//! }"#
+//! .parse()
+//! .unwrap();
+//!
+//! let diagnostics = validate(&geojson);
+//! assert_eq!(diagnostics.len(), 2);
+//! assert_eq!(diagnostics[0].severity, Severity::Warning);
+//! assert_eq!(diagnostics[0].pointer, "/geometry/coordinates/0");
+//! assert_eq!(diagnostics[0].problem, Problem::WrongWinding { exterior: true });
+//! assert_eq!(diagnostics[1].problem, Problem::Crs);
+//! ```
+use crate::util::Extent;
+use crate::{
+    Bbox, Feature, FeatureCollection, FeatureReader, GeoJson, Geometry, JsonObject, Position,
+    Result, Value,
+};
+
+use std::fmt;
+use std::io::Read;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The object doesn't follow a recommendation of the specification.
+    Warning,
+    /// The object isn't valid GeoJSON.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A problem found by [`validate`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    /// A LineString has fewer than two positions.
+    ///
+    /// [GeoJSON Format Specification § 3.1.4](https://tools.ietf.org/html/rfc7946#section-3.1.4)
+    LineStringTooShort(usize),
+    /// A linear ring has fewer than four positions.
+    ///
+    /// [GeoJSON Format Specification § 3.1.6](https://tools.ietf.org/html/rfc7946#section-3.1.6)
+    RingTooShort(usize),
+    /// The first and last positions of a linear ring differ.
+    ///
+    /// [GeoJSON Format Specification § 3.1.6](https://tools.ietf.org/html/rfc7946#section-3.1.6)
+    RingNotClosed,
+    /// A linear ring doesn't follow the right-hand rule: exterior rings should be
+    /// counterclockwise, and holes clockwise.
+    ///
+    /// [GeoJSON Format Specification § 3.1.6](https://tools.ietf.org/html/rfc7946#section-3.1.6)
+    WrongWinding {
+        /// Whether the ring is the exterior ring of its polygon.
+        exterior: bool,
+    },
+    /// A bbox doesn't have `2 * n` values for some `n` of at least 2.
+    ///
+    /// [GeoJSON Format Specification § 5](https://tools.ietf.org/html/rfc7946#section-5)
+    BboxWrongLength(usize),
+    /// Some positions of the object lie outside of its bbox.
+    ///
+    /// [GeoJSON Format Specification § 5](https://tools.ietf.org/html/rfc7946#section-5)
+    BboxDoesNotContainGeometry,
+    /// A longitude is outside of `-180..=180`.
+    ///
+    /// [GeoJSON Format Specification § 4](https://tools.ietf.org/html/rfc7946#section-4)
+    LongitudeOutOfRange(f64),
+    /// A latitude is outside of `-90..=90`.
+    ///
+    /// [GeoJSON Format Specification § 4](https://tools.ietf.org/html/rfc7946#section-4)
+    LatitudeOutOfRange(f64),
+    /// A foreign member has the name of a member of another kind of object, like `coordinates`
+    /// on a Feature.
+    ///
+    /// [GeoJSON Format Specification § 7.1](https://tools.ietf.org/html/rfc7946#section-7.1)
+    DisallowedMember(String),
+    /// The object has a `crs` member, which was removed from the specification.
+    ///
+    /// [GeoJSON Format Specification § 4](https://tools.ietf.org/html/rfc7946#section-4)
+    Crs,
+}
+
+impl Problem {
+    /// How serious the problem is.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Problem::WrongWinding { .. } | Problem::Crs => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::LineStringTooShort(len) => write!(
+                f,
+                "a LineString must have two or more positions, but has {}",
+                len
+            ),
+            Problem::RingTooShort(len) => write!(
+                f,
+                "a linear ring must have four or more positions, but has {}",
+                len
+            ),
+            Problem::RingNotClosed => f.write_str("a linear ring must be closed"),
+            Problem::WrongWinding { exterior: true } => {
+                f.write_str("an exterior ring should be counterclockwise")
+            }
+            Problem::WrongWinding { exterior: false } => f.write_str("a hole should be clockwise"),
+            Problem::BboxWrongLength(len) => write!(
+                f,
+                "a bbox must have 2*n values, where n is at least 2, but has {}",
+                len
+            ),
+            Problem::BboxDoesNotContainGeometry => {
+                f.write_str("the bbox doesn't contain all of the object's positions")
+            }
+            Problem::LongitudeOutOfRange(longitude) => {
+                write!(f, "longitude {} is out of range", longitude)
+            }
+            Problem::LatitudeOutOfRange(latitude) => {
+                write!(f, "latitude {} is out of range", latitude)
+            }
+            Problem::DisallowedMember(name) => write!(f, "`{}` isn't allowed here", name),
+            Problem::Crs => f.write_str("the `crs` member is no longer part of GeoJSON"),
+        }
+    }
+}
+
+/// A [`Problem`] found at a JSON pointer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// How serious the problem is, which is determined by the kind of problem.
+    pub severity: Severity,
+    /// The JSON pointer to the member with the problem, e.g. `/features/0/geometry/coordinates/1`.
+    pub pointer: String,
+    /// What the problem is.
+    pub problem: Problem,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at `{}`: {}",
+            self.severity, self.pointer, self.problem
+        )
+    }
+}
+
+/// Check `geojson` for conformance with RFC 7946, returning everything that was found, in
+/// document order.
+pub fn validate(geojson: &GeoJson) -> Vec<Diagnostic> {
+    let mut validator = Validator::default();
+    match geojson {
+        GeoJson::Geometry(geometry) => {
+            validator.geometry(geometry, "");
+        }
+        GeoJson::Feature(feature) => {
+            validator.feature(feature, "");
+        }
+        GeoJson::FeatureCollection(feature_collection) => {
+            validator.feature_collection(feature_collection)
+        }
+    }
+    validator.diagnostics
+}
+
+/// Check the FeatureCollection read by `reader` for conformance with RFC 7946, one feature at a
+/// time.
+///
+/// This fails if the FeatureCollection can't be read. If the FeatureCollection's `bbox` crosses
+/// the antimeridian, its longitudes aren't checked against the features.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::validate::{validate_feature_reader, Problem};
+/// use geojson::FeatureReader;
+///
+/// let feature_collection_string = r#"{
+///     "type": "FeatureCollection",
+///     "features": [
+///         { "type": "Feature", "geometry": { "type": "Point", "coordinates": [1, 2] },
+///           "properties": null },
+///         { "type": "Feature", "geometry": { "type": "LineString", "coordinates": [[1, 2]] },
+///           "properties": null }
+///     ]
+/// }"#
+/// .as_bytes();
+///
+/// let diagnostics =
+///     validate_feature_reader(FeatureReader::from_reader(feature_collection_string)).unwrap();
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].pointer, "/features/1/geometry/coordinates");
+/// assert_eq!(diagnostics[0].problem, Problem::LineStringTooShort(1));
+/// ```
+pub fn validate_feature_reader<R: Read>(reader: FeatureReader<R>) -> Result<Vec<Diagnostic>> {
+    let mut validator = Validator::default();
+    let mut extent = Extent::default();
+    let mut features = reader.features();
+    for (index, feature) in (&mut features).enumerate() {
+        extent.union(&validator.feature(&feature?, &format!("/features/{}", index)));
+    }
+
+    let members = features.collection_members();
+    validator.members(
+        members.foreign_members.as_ref(),
+        &["coordinates", "geometries", "geometry", "properties"],
+        "",
+    );
+    if let Some(bbox) = &members.bbox {
+        validator.bbox(bbox, &extent, "");
+    }
+    Ok(validator.diagnostics)
+}
+
+#[derive(Default)]
+struct Validator {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator {
+    fn report(&mut self, pointer: String, problem: Problem) {
+        self.diagnostics.push(Diagnostic {
+            severity: problem.severity(),
+            pointer,
+            problem,
+        });
+    }
+
+    fn feature_collection(&mut self, feature_collection: &FeatureCollection) {
+        let mut extent = Extent::default();
+        for (index, feature) in feature_collection.features.iter().enumerate() {
+            extent.union(&self.feature(feature, &format!("/features/{}", index)));
+        }
+        self.members(
+            feature_collection.foreign_members.as_ref(),
+            &["coordinates", "geometries", "geometry", "properties"],
+            "",
+        );
+        if let Some(bbox) = &feature_collection.bbox {
+            self.bbox(bbox, &extent, "");
+        }
+    }
+
+    fn feature(&mut self, feature: &Feature, pointer: &str) -> Extent {
+        let extent = match &feature.geometry {
+            Some(geometry) => self.geometry(geometry, &format!("{}/geometry", pointer)),
+            None => Extent::default(),
+        };
+        self.members(
+            feature.foreign_members.as_ref(),
+            &["coordinates", "geometries", "features"],
+            pointer,
+        );
+        if let Some(bbox) = &feature.bbox {
+            self.bbox(bbox, &extent, pointer);
+        }
+        extent
+    }
+
+    fn geometry(&mut self, geometry: &Geometry, pointer: &str) -> Extent {
+        let mut extent = Extent::default();
+        let coordinates = format!("{}/coordinates", pointer);
+        match &geometry.value {
+            Value::Point(position) => self.position(position, &coordinates, &mut extent),
+            Value::MultiPoint(positions) => self.positions(positions, &coordinates, &mut extent),
+            Value::LineString(line_string) => {
+                self.line_string(line_string, &coordinates, &mut extent)
+            }
+            Value::MultiLineString(line_strings) => {
+                for (index, line_string) in line_strings.iter().enumerate() {
+                    let pointer = format!("{}/{}", coordinates, index);
+                    self.line_string(line_string, &pointer, &mut extent);
+                }
+            }
+            Value::Polygon(polygon) => self.polygon(polygon, &coordinates, &mut extent),
+            Value::MultiPolygon(polygons) => {
+                for (index, polygon) in polygons.iter().enumerate() {
+                    let pointer = format!("{}/{}", coordinates, index);
+                    self.polygon(polygon, &pointer, &mut extent);
+                }
+            }
+            Value::GeometryCollection(geometries) => {
+                for (index, geometry) in geometries.iter().enumerate() {
+                    let pointer = format!("{}/geometries/{}", pointer, index);
+                    extent.union(&self.geometry(geometry, &pointer));
+                }
+            }
+        }
+
+        // The member holding the value of the other kind of geometry.
+        let other_value_member = match geometry.value {
+            Value::GeometryCollection(_) => "coordinates",
+            _ => "geometries",
+        };
+        self.members(
+            geometry.foreign_members.as_ref(),
+            &[other_value_member, "geometry", "properties", "features"],
+            pointer,
+        );
+        if let Some(bbox) = &geometry.bbox {
+            self.bbox(bbox, &extent, pointer);
+        }
+        extent
+    }
+
+    fn polygon(&mut self, polygon: &[Vec<Position>], pointer: &str, extent: &mut Extent) {
+        for (index, ring) in polygon.iter().enumerate() {
+            let pointer = format!("{}/{}", pointer, index);
+            self.ring(ring, index == 0, &pointer, extent);
+        }
+    }
+
+    fn ring(&mut self, ring: &[Position], exterior: bool, pointer: &str, extent: &mut Extent) {
+        if ring.len() < 4 {
+            self.report(pointer.to_string(), Problem::RingTooShort(ring.len()));
+        } else if ring.first() != ring.last() {
+            self.report(pointer.to_string(), Problem::RingNotClosed);
+        } else {
+            let area = signed_area(ring);
+            if (exterior && area < 0.0) || (!exterior && area > 0.0) {
+                self.report(pointer.to_string(), Problem::WrongWinding { exterior });
+            }
+        }
+        self.positions(ring, pointer, extent);
+    }
+
+    fn line_string(&mut self, line_string: &[Position], pointer: &str, extent: &mut Extent) {
+        if line_string.len() < 2 {
+            self.report(
+                pointer.to_string(),
+                Problem::LineStringTooShort(line_string.len()),
+            );
+        }
+        self.positions(line_string, pointer, extent);
+    }
+
+    fn positions(&mut self, positions: &[Position], pointer: &str, extent: &mut Extent) {
+        for (index, position) in positions.iter().enumerate() {
+            self.position(position, &format!("{}/{}", pointer, index), extent);
+        }
+    }
+
+    fn position(&mut self, position: &Position, pointer: &str, extent: &mut Extent) {
+        if let Some(&longitude) = position.first() {
+            if !(-180.0..=180.0).contains(&longitude) {
+                self.report(
+                    format!("{}/0", pointer),
+                    Problem::LongitudeOutOfRange(longitude),
+                );
+            }
+        }
+        if let Some(&latitude) = position.get(1) {
+            if !(-90.0..=90.0).contains(&latitude) {
+                self.report(
+                    format!("{}/1", pointer),
+                    Problem::LatitudeOutOfRange(latitude),
+                );
+            }
+        }
+        extent.add(position);
+    }
+
+    /// Check that an object's bbox has a valid length, and contains the object's `extent`.
+    fn bbox(&mut self, bbox: &Bbox, extent: &Extent, pointer: &str) {
+        let pointer = format!("{}/bbox", pointer);
+        if bbox.len() < 4 || !bbox.len().is_multiple_of(2) {
+            self.report(pointer, Problem::BboxWrongLength(bbox.len()));
+            return;
+        }
+        let dimensions = bbox.len() / 2;
+        let contains = (0..dimensions.min(extent.min.len())).all(|dimension| {
+            let (low, high) = (bbox[dimension], bbox[dimensions + dimension]);
+            // A bbox whose west edge is east of its east edge crosses the antimeridian, and
+            // the extent doesn't tell whether the longitudes fall on either side of it.
+            (dimension == 0 && low > high)
+                || (low <= extent.min[dimension] && extent.max[dimension] <= high)
+        });
+        if !contains {
+            self.report(pointer, Problem::BboxDoesNotContainGeometry);
+        }
+    }
+
+    /// Check the foreign members of an object for `crs`, and for any of `disallowed`.
+    fn members(
+        &mut self,
+        foreign_members: Option<&JsonObject>,
+        disallowed: &[&str],
+        pointer: &str,
+    ) {
+        for name in foreign_members.into_iter().flat_map(JsonObject::keys) {
+            if name == "crs" {
+                self.report(format!("{}/crs", pointer), Problem::Crs);
+            } else if disallowed.contains(&name.as_str()) {
+                self.report(
+                    format!("{}/{}", pointer, name),
+                    Problem::DisallowedMember(name.clone()),
+                );
+            }
+        }
+    }
+}
+
+/// Twice the signed area of a closed ring, which is positive if it's counterclockwise.
+fn signed_area(ring: &[Position]) -> f64 {
+    ring.windows(2)
+        .map(|pair| match (&pair[0][..], &pair[1][..]) {
+            ([x0, y0, ..], [x1, y1, ..]) => x0 * y1 - x1 * y0,
+            _ => 0.0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::JsonValue;
+    use serde_json::json;
+
+    fn problems(geojson: JsonValue) -> Vec<(String, Problem)> {
+        validate(&GeoJson::from_json_value(geojson).unwrap())
+            .into_iter()
+            .map(|diagnostic| (diagnostic.pointer, diagnostic.problem))
+            .collect()
+    }
+
+    #[test]
+    fn valid() {
+        let geojson = json!({
+            "type": "FeatureCollection",
+            "bbox": [-10, -10, 10, 10],
+            "features": [{
+                "type": "Feature",
+                "bbox": [-10, -10, 10, 10],
+                "geometry": {
+                    "type": "Polygon",
+                    "coordinates": [
+                        [[-10, -10], [10, -10], [10, 10], [-10, 10], [-10, -10]],
+                        [[-1, -1], [-1, 1], [1, 1], [1, -1], [-1, -1]]
+                    ]
+                },
+                "properties": null
+            }]
+        });
+        assert_eq!(problems(geojson), []);
+    }
+
+    #[test]
+    fn geometries() {
+        let geojson = json!({
+            "type": "GeometryCollection",
+            "geometries": [
+                { "type": "LineString", "coordinates": [[1, 2]] },
+                { "type": "Polygon", "coordinates": [
+                    [[0, 0], [1, 0], [1, 1], [0, 1]],
+                    [[0, 0], [1, 0], [0, 0]],
+                    [[0, 0], [1, 0], [1, 1], [0, 0]]
+                ] },
+                { "type": "MultiPoint", "coordinates": [[181, 0], [0, -91, 1]] }
+            ]
+        });
+        assert_eq!(
+            problems(geojson),
+            [
+                (
+                    "/geometries/0/coordinates".to_string(),
+                    Problem::LineStringTooShort(1)
+                ),
+                (
+                    "/geometries/1/coordinates/0".to_string(),
+                    Problem::RingNotClosed
+                ),
+                (
+                    "/geometries/1/coordinates/1".to_string(),
+                    Problem::RingTooShort(3)
+                ),
+                (
+                    "/geometries/1/coordinates/2".to_string(),
+                    Problem::WrongWinding { exterior: false }
+                ),
+                (
+                    "/geometries/2/coordinates/0/0".to_string(),
+                    Problem::LongitudeOutOfRange(181.0)
+                ),
+                (
+                    "/geometries/2/coordinates/1/1".to_string(),
+                    Problem::LatitudeOutOfRange(-91.0)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn bboxes() {
+        let geojson = json!({
+            "type": "FeatureCollection",
+            "bbox": [0, 0, 1],
+            "features": [
+                {
+                    "type": "Feature",
+                    "bbox": [0, 0, 1, 1],
+                    "geometry": { "type": "Point", "coordinates": [2, 0.5] },
+                    "properties": null
+                },
+                {
+                    "type": "Feature",
+                    "bbox": [170, 0, -170, 1],
+                    "geometry": { "type": "MultiPoint", "coordinates": [[175, 0.5], [-175, 0.5]] },
+                    "properties": null
+                },
+                {
+                    "type": "Feature",
+                    "bbox": [0, 0, 0, 1, 1, 1],
+                    "geometry": { "type": "Point", "coordinates": [0.5, 0.5, 2] },
+                    "properties": null
+                }
+            ]
+        });
+        assert_eq!(
+            problems(geojson),
+            [
+                (
+                    "/features/0/bbox".to_string(),
+                    Problem::BboxDoesNotContainGeometry
+                ),
+                (
+                    "/features/2/bbox".to_string(),
+                    Problem::BboxDoesNotContainGeometry
+                ),
+                ("/bbox".to_string(), Problem::BboxWrongLength(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn members() {
+        let geojson = json!({
+            "type": "Feature",
+            "geometry": {
+                "type": "Point",
+                "coordinates": [0, 0],
+                "properties": {},
+                "crs": null
+            },
+            "properties": null,
+            "coordinates": [0, 0],
+            "title": "allowed"
+        });
+        assert_eq!(
+            problems(geojson),
+            [
+                ("/geometry/crs".to_string(), Problem::Crs),
+                (
+                    "/geometry/properties".to_string(),
+                    Problem::DisallowedMember("properties".to_string())
+                ),
+                (
+                    "/coordinates".to_string(),
+                    Problem::DisallowedMember("coordinates".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn feature_reader() {
+        let geojson = json!({
+            "type": "FeatureCollection",
+            "features": [
+                { "type": "Feature", "geometry": { "type": "Point", "coordinates": [0, 0] },
+                  "properties": null },
+                { "type": "Feature", "geometry": { "type": "Point", "coordinates": [5, 5] },
+                  "properties": null }
+            ],
+            "bbox": [0, 0, 1, 1],
+            "geometry": null
+        });
+        let geojson_string = geojson.to_string();
+        let streamed =
+            validate_feature_reader(FeatureReader::from_reader(geojson_string.as_bytes())).unwrap();
+        assert_eq!(streamed, validate(&geojson_string.parse().unwrap()));
+        assert_eq!(streamed.len(), 2);
+        assert_eq!(
+            streamed[1].to_string(),
+            "error at `/bbox`: the bbox doesn't contain all of the object's positions"
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{InlinePosition, JsonValue};
+
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// The largest power of ten which an `f64` can hold.
+const MAX_PRECISION: u32 = 308;
+
+/// Options for writing GeoJSON.
+///
+/// RFC 7946 [recommends](https://tools.ietf.org/html/rfc7946#section-11.2) that coordinates have
+/// no more than 6 decimal places, which is about 10 centimeters, and more digits only add noise
+/// and size to the output. `coordinate_precision` rounds the coordinates of geometries when
+/// they're written, leaving the numbers in properties and foreign members untouched.
+///
+/// The options are used by [`WithOptions`], which displays or serializes a GeoJSON object, by the
+/// `ser::to_feature_collection_*_with_options` functions, and by
+/// [`FeatureWriter::with_options`](crate::FeatureWriter::with_options).
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Geometry, Value, WithOptions, WriteOptions};
+///
+/// let geometry = Geometry {
+///     bbox: Some(vec![1.23456789, 2.0, 1.23456789, 2.0]),
+///     ..Geometry::new(Value::Point(vec![1.23456789, 2.0]))
+/// };
+/// let options = WriteOptions {
+///     coordinate_precision: Some(3),
+///     ..WriteOptions::default()
+/// };
+/// assert_eq!(
+///     WithOptions::new(&geometry, &options).to_string(),
+///     r#"{"bbox":[1.23456789,2.0,1.23456789,2.0],"coordinates":[1.235,2.0],"type":"Point"}"#
+/// );
+///
+/// let options = WriteOptions {
+///     round_bbox: true,
+///     ..options
+/// };
+/// assert_eq!(
+///     WithOptions::new(&geometry, &options).to_string(),
+///     r#"{"bbox":[1.235,2.0,1.235,2.0],"coordinates":[1.235,2.0],"type":"Point"}"#
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// The number of decimal places to round coordinates to, or `None` to write them as they are.
+    pub coordinate_precision: Option<u32>,
+    /// Whether to round the values of bboxes to `coordinate_precision` too.
+    pub round_bbox: bool,
+}
+
+impl WriteOptions {
+    /// The factor which rounding to `coordinate_precision` scales by, if any.
+    pub(crate) fn scale(&self) -> Option<f64> {
+        // Clamped, since `as i32` would wrap larger values around to negative powers.
+        self.coordinate_precision
+            .map(|precision| 10f64.powi(precision.min(MAX_PRECISION) as i32))
+    }
+
+    /// The factor to round bbox values with, if they're rounded.
+    pub(crate) fn bbox_scale(&self) -> Option<f64> {
+        self.scale().filter(|_| self.round_bbox)
+    }
+
+    /// Round the coordinates (and, if enabled, bboxes) of a geometry which has already been
+    /// converted to JSON.
+    pub(crate) fn round_geometry_json(&self, geometry: &mut JsonValue) {
+        let (scale, object) = match (self.scale(), geometry) {
+            (Some(scale), JsonValue::Object(object)) => (scale, object),
+            _ => return,
+        };
+        if let Some(coordinates) = object.get_mut("coordinates") {
+            round_json(coordinates, scale);
+        }
+        if let Some(bbox) = object.get_mut("bbox").filter(|_| self.round_bbox) {
+            round_json(bbox, scale);
+        }
+        if let Some(JsonValue::Array(geometries)) = object.get_mut("geometries") {
+            for geometry in geometries {
+                self.round_geometry_json(geometry);
+            }
+        }
+    }
+}
+
+/// Round `value` to the number of decimal places given by `scale`, which is a power of ten.
+pub(crate) fn round(value: f64, scale: f64) -> f64 {
+    let scaled = value * scale;
+    // From 2^52 on, every `f64` is a whole number, so there's nothing to round, and scaling back
+    // would only add error.
+    if scaled.abs() < 4_503_599_627_370_496.0 {
+        scaled.round() / scale
+    } else {
+        value
+    }
+}
+
+/// Numbers, or (possibly nested) vectors of numbers, serialized rounded with a scale from
+/// [`WriteOptions::scale`].
+pub(crate) struct Rounded<'a, T>(pub(crate) &'a T, pub(crate) f64);
+
+impl Serialize for Rounded<'_, f64> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(round(*self.0, self.1))
+    }
+}
+
+impl Serialize for Rounded<'_, InlinePosition> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.0.iter().map(|value| Rounded(value, self.1)))
+    }
+}
+
+impl<T> Serialize for Rounded<'_, Vec<T>>
+where
+    for<'b> Rounded<'b, T>: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.0.iter().map(|value| Rounded(value, self.1)))
+    }
+}
+
+/// Round the floating point numbers in (possibly nested) arrays. Integers are left as they are, so
+/// that they're still written without a decimal point.
+fn round_json(value: &mut JsonValue, scale: f64) {
+    match value {
+        JsonValue::Array(values) => {
+            for value in values {
+                round_json(value, scale);
+            }
+        }
+        JsonValue::Number(number) if number.is_f64() => {
+            let rounded = number
+                .as_f64()
+                .map(|number| round(number, scale))
+                .and_then(serde_json::Number::from_f64);
+            if let Some(rounded) = rounded {
+                *number = rounded;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A GeoJSON object which is displayed or serialized with [`WriteOptions`].
+///
+/// This can wrap a [`GeoJson`](crate::GeoJson), [`Geometry`](crate::Geometry),
+/// [`Feature`](crate::Feature) or [`FeatureCollection`](crate::FeatureCollection). In formats which
+/// aren't human readable, the object is serialized as usual.
+pub struct WithOptions<'a, T: ?Sized> {
+    pub(crate) value: &'a T,
+    pub(crate) options: &'a WriteOptions,
+}
+
+impl<'a, T: ?Sized> WithOptions<'a, T> {
+    pub fn new(value: &'a T, options: &'a WriteOptions) -> Self {
+        WithOptions { value, options }
+    }
+}
+
+impl<T: ?Sized> fmt::Display for WithOptions<'_, T>
+where
+    Self: Serialize,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ::serde_json::to_string(self)
+            .map_err(|_| fmt::Error)
+            .and_then(|s| f.write_str(&s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Feature, FeatureCollection, FeatureWriter, GeoJson, Geometry, Value};
+    use serde_json::json;
+
+    fn feature_collection() -> FeatureCollection {
+        let geometry = Geometry::new(Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![1.0000004, -2.9999996])),
+            Geometry {
+                bbox: Some(vec![0.123456789, 0.0, 1.0, 1.0]),
+                ..Geometry::new(Value::Polygon(vec![vec![
+                    vec![0.123456789, 0.0],
+                    vec![1.0, 0.0],
+                    vec![1.0, 1.0],
+                    vec![0.123456789, 0.0],
+                ]]))
+            },
+        ]));
+        let feature = Feature {
+            bbox: Some(vec![0.123456789, -2.9999996, 1.0000004, 1.0]),
+            geometry: Some(geometry),
+            properties: json!({ "area": 0.123456789 }).as_object().cloned(),
+            ..Feature::default()
+        };
+        FeatureCollection {
+            bbox: None,
+            features: vec![feature],
+            foreign_members: json!({ "scale": 1.23456789 }).as_object().cloned(),
+        }
+    }
+
+    fn expected(round_bbox: bool) -> JsonValue {
+        let bbox = if round_bbox {
+            json!([0.1235, 0.0, 1.0, 1.0])
+        } else {
+            json!([0.123456789, 0.0, 1.0, 1.0])
+        };
+        let feature_bbox = if round_bbox {
+            json!([0.1235, -3.0, 1.0, 1.0])
+        } else {
+            json!([0.123456789, -2.9999996, 1.0000004, 1.0])
+        };
+        json!({
+            "type": "FeatureCollection",
+            "features": [{
+                "type": "Feature",
+                "bbox": feature_bbox,
+                "geometry": {
+                    "type": "GeometryCollection",
+                    "geometries": [
+                        { "type": "Point", "coordinates": [1.0, -3.0] },
+                        {
+                            "type": "Polygon",
+                            "bbox": bbox,
+                            "coordinates": [[[0.1235, 0.0], [1.0, 0.0], [1.0, 1.0], [0.1235, 0.0]]]
+                        }
+                    ]
+                },
+                "properties": { "area": 0.123456789 }
+            }],
+            "scale": 1.23456789
+        })
+    }
+
+    #[test]
+    fn round_coordinates() {
+        let geojson = GeoJson::from(feature_collection());
+        for round_bbox in [false, true] {
+            let options = WriteOptions {
+                coordinate_precision: Some(4),
+                round_bbox,
+            };
+            let written: JsonValue =
+                serde_json::from_str(&WithOptions::new(&geojson, &options).to_string()).unwrap();
+            assert_eq!(written, expected(round_bbox));
+        }
+    }
+
+    #[test]
+    fn large_precision() {
+        let geojson = GeoJson::from(feature_collection());
+        for precision in [20, MAX_PRECISION, u32::MAX] {
+            let options = WriteOptions {
+                coordinate_precision: Some(precision),
+                round_bbox: true,
+            };
+            assert_eq!(
+                WithOptions::new(&geojson, &options).to_string(),
+                geojson.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn default_options() {
+        let geojson = GeoJson::from(feature_collection());
+        assert_eq!(
+            WithOptions::new(&geojson, &WriteOptions::default()).to_string(),
+            geojson.to_string()
+        );
+    }
+
+    #[test]
+    fn feature_writer() {
+        let options = WriteOptions {
+            coordinate_precision: Some(4),
+            round_bbox: true,
+        };
+        let mut output = vec![];
+        {
+            let mut writer = FeatureWriter::from_writer(&mut output).with_options(options.clone());
+            writer
+                .write_feature(&feature_collection().features[0])
+                .unwrap();
+        }
+        let written: JsonValue = serde_json::from_slice(&output).unwrap();
+        assert_eq!(written["features"], expected(true)["features"]);
+
+        #[derive(Serialize)]
+        struct Record {
+            geometry: Geometry,
+            area: f64,
+        }
+        let record = Record {
+            geometry: feature_collection().features[0].geometry.clone().unwrap(),
+            area: 0.123456789,
+        };
+        let written =
+            crate::ser::to_feature_collection_string_with_options(&[record], &options).unwrap();
+        let written: JsonValue = serde_json::from_str(&written).unwrap();
+        let mut expected_feature = expected(true)["features"][0].clone();
+        expected_feature.as_object_mut().unwrap().remove("bbox");
+        assert_eq!(written["features"][0], expected_feature);
+    }
+}